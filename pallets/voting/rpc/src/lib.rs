@@ -0,0 +1,209 @@
+//! RPC interface for pallet-voting.
+//!
+//! Exposes the `VotingApi` runtime API as `voting_*` methods, so front-ends don't need to decode
+//! the raw storage of the pallet.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+pub use pallet_voting_runtime_api::{
+	Conviction, ProposalInfo, ProposalStatus, Tally, VoteInfo, VoteKind,
+	VotingApi as VotingRuntimeApi,
+};
+
+/// Voting RPC methods.
+#[rpc(client, server)]
+pub trait VotingApi<BlockHash, AccountId, Balance, BlockNumber, Hash, ProposalId> {
+	/// Information about a proposal, or `null` if it doesn't exist.
+	#[method(name = "voting_proposal")]
+	fn proposal(
+		&self,
+		proposal_id: ProposalId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<ProposalInfo<Hash, Balance, BlockNumber>>>;
+
+	/// The votes cast on a proposal, or `null` if it doesn't exist.
+	#[method(name = "voting_tally")]
+	fn tally(
+		&self,
+		proposal_id: ProposalId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<Tally<Balance>>>;
+
+	/// The ids of the proposals that haven't been closed yet.
+	#[method(name = "voting_activeProposals")]
+	fn active_proposals(&self, at: Option<BlockHash>) -> RpcResult<Vec<ProposalId>>;
+
+	/// The votes of an account whose frozen tokens haven't been claimed yet.
+	#[method(name = "voting_voterHistory")]
+	fn voter_history(
+		&self,
+		who: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<VoteInfo<ProposalId, Balance, BlockNumber>>>;
+
+	/// Whether the account is registered as a voter.
+	#[method(name = "voting_isRegistered")]
+	fn is_registered(&self, who: AccountId, at: Option<BlockHash>) -> RpcResult<bool>;
+
+	/// The tokens frozen to cast `votes` votes, or `null` on overflow.
+	#[method(name = "voting_voteCost")]
+	fn vote_cost(&self, votes: Balance, at: Option<BlockHash>) -> RpcResult<Option<Balance>>;
+
+	/// The tokens an account is missing to cast `votes` votes on a proposal, zero if it can
+	/// afford them, or `null` if the proposal doesn't exist or on overflow.
+	#[method(name = "voting_voteShortfall")]
+	fn vote_shortfall(
+		&self,
+		who: AccountId,
+		proposal_id: ProposalId,
+		votes: Balance,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<Balance>>;
+
+	/// The tokens of an account frozen by its votes.
+	#[method(name = "voting_frozenBalance")]
+	fn frozen_balance(&self, who: AccountId, at: Option<BlockHash>) -> RpcResult<Balance>;
+}
+
+/// Provides RPC methods to query the state of pallet-voting.
+pub struct Voting<C, Block> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<Block>,
+}
+
+impl<C, Block> Voting<C, Block> {
+	/// Creates a new instance of the Voting RPC handler.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to the runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+/// Converts a runtime API error into an RPC error.
+fn runtime_error(e: impl ToString) -> CallError {
+	CallError::Custom(ErrorObject::owned(
+		Error::RuntimeError.into(),
+		"Unable to query the voting state.",
+		Some(e.to_string()),
+	))
+}
+
+impl<C, Block, AccountId, Balance, BlockNumber, Hash, ProposalId>
+	VotingApiServer<<Block as BlockT>::Hash, AccountId, Balance, BlockNumber, Hash, ProposalId>
+	for Voting<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: VotingRuntimeApi<Block, AccountId, Balance, BlockNumber, Hash, ProposalId>,
+	AccountId: Codec + Send + Sync + DeserializeOwned + 'static,
+	Balance: Codec + Send + Sync + Serialize + DeserializeOwned + 'static,
+	BlockNumber: Codec + Send + Sync + Serialize + 'static,
+	Hash: Codec + Send + Sync + Serialize + 'static,
+	ProposalId: Codec + Send + Sync + Serialize + DeserializeOwned + 'static,
+{
+	fn proposal(
+		&self,
+		proposal_id: ProposalId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<ProposalInfo<Hash, Balance, BlockNumber>>> {
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.proposal(at_hash, proposal_id)
+			.map_err(|e| runtime_error(e).into())
+	}
+
+	fn tally(
+		&self,
+		proposal_id: ProposalId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<Tally<Balance>>> {
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.tally(at_hash, proposal_id)
+			.map_err(|e| runtime_error(e).into())
+	}
+
+	fn active_proposals(&self, at: Option<Block::Hash>) -> RpcResult<Vec<ProposalId>> {
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.active_proposals(at_hash)
+			.map_err(|e| runtime_error(e).into())
+	}
+
+	fn voter_history(
+		&self,
+		who: AccountId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Vec<VoteInfo<ProposalId, Balance, BlockNumber>>> {
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.voter_history(at_hash, who)
+			.map_err(|e| runtime_error(e).into())
+	}
+
+	fn is_registered(&self, who: AccountId, at: Option<Block::Hash>) -> RpcResult<bool> {
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.is_registered(at_hash, who)
+			.map_err(|e| runtime_error(e).into())
+	}
+
+	fn vote_cost(&self, votes: Balance, at: Option<Block::Hash>) -> RpcResult<Option<Balance>> {
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.vote_cost(at_hash, votes)
+			.map_err(|e| runtime_error(e).into())
+	}
+
+	fn vote_shortfall(
+		&self,
+		who: AccountId,
+		proposal_id: ProposalId,
+		votes: Balance,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<Balance>> {
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.vote_shortfall(at_hash, who, proposal_id, votes)
+			.map_err(|e| runtime_error(e).into())
+	}
+
+	fn frozen_balance(&self, who: AccountId, at: Option<Block::Hash>) -> RpcResult<Balance> {
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.frozen_balance(at_hash, who)
+			.map_err(|e| runtime_error(e).into())
+	}
+}
@@ -0,0 +1,50 @@
+//! Runtime API definition for pallet-voting.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+pub use pallet_voting::{
+	conviction::Conviction, ProposalInfo, ProposalStatus, Tally, VoteInfo, VoteKind,
+};
+
+sp_api::decl_runtime_apis! {
+	/// Queries the proposals, tallies and voters of pallet-voting.
+	pub trait VotingApi<AccountId, Balance, BlockNumber, Hash, ProposalId> where
+		AccountId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+		Hash: Codec,
+		ProposalId: Codec,
+	{
+		/// Information about a proposal, or `None` if it doesn't exist.
+		fn proposal(proposal_id: ProposalId) -> Option<ProposalInfo<Hash, Balance, BlockNumber>>;
+
+		/// The votes cast on a proposal, or `None` if it doesn't exist.
+		fn tally(proposal_id: ProposalId) -> Option<Tally<Balance>>;
+
+		/// The ids of the proposals that haven't been closed yet.
+		fn active_proposals() -> Vec<ProposalId>;
+
+		/// The votes of an account whose frozen tokens haven't been claimed yet.
+		fn voter_history(who: AccountId) -> Vec<VoteInfo<ProposalId, Balance, BlockNumber>>;
+
+		/// Whether the account is registered as a voter.
+		fn is_registered(who: AccountId) -> bool;
+
+		/// The tokens frozen to cast `votes` votes, or `None` on overflow.
+		fn vote_cost(votes: Balance) -> Option<Balance>;
+
+		/// The tokens an account is missing to cast `votes` votes on a proposal, zero if it can
+		/// afford them, or `None` if the proposal doesn't exist or on overflow.
+		fn vote_shortfall(
+			who: AccountId,
+			proposal_id: ProposalId,
+			votes: Balance,
+		) -> Option<Balance>;
+
+		/// The tokens of an account frozen by its votes.
+		fn frozen_balance(who: AccountId) -> Balance;
+	}
+}
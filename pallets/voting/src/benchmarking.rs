@@ -5,30 +5,587 @@ use super::*;
 #[allow(unused)]
 use crate::Pallet as Voting;
 use frame_benchmarking::v2::*;
+use frame_support::sp_runtime::traits::One;
 use frame_system::RawOrigin;
+use sp_std::vec;
+use tracks::TrackInfo;
+
+const SEED: u32 = 0;
+
+/// Creates a registered account with enough tokens to cast any benchmarked vote.
+fn funded_voter<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let voter: T::AccountId = account(name, index, SEED);
+	let amount =
+		<T::NativeBalance as fungible::Inspect<_>>::minimum_balance() + 1_000_000_000u32.into();
+	<T::NativeBalance as fungible::Mutate<_>>::set_balance(&voter, amount);
+	RegisteredAccounts::<T>::insert(&voter, RegistrationKind::Manual);
+	voter
+}
+
+/// Proposal metadata with the longest title and a description of `len` bytes stored in the
+/// pallet.
+fn metadata<T: Config>(len: u32) -> ProposalMetadata<T> {
+	let description = BoundedVec::truncate_from(vec![1u8; len as usize]);
+	ProposalMetadata {
+		title: BoundedVec::truncate_from(vec![0u8; T::MaxTitleLength::get() as usize]),
+		description: ProposalDescription::Inline(description),
+	}
+}
+
+/// The track benchmarked proposals are made on.
+fn track<T: Config>() -> (TrackId, TrackInfo<BalanceOf<T>, BlockNumberFor<T>, AssetIdOf<T>>) {
+	T::Tracks::tracks()
+		.into_iter()
+		.next()
+		.expect("the runtime needs at least one track")
+}
+
+/// Creates a proposal from `proposer` and returns its id.
+fn create_proposal<T: Config>(proposer: &T::AccountId) -> Result<T::ProposalId, BenchmarkError> {
+	let proposal_id = ProposalIndex::<T>::get();
+	Voting::<T>::make_proposal(
+		RawOrigin::Signed(proposer.clone()).into(),
+		track::<T>().0,
+		metadata::<T>(32),
+		None,
+	)?;
+	Ok(proposal_id)
+}
+
+/// Casts `votes` "Aye" votes from `voter` on each proposal.
+fn vote_on<T: Config>(
+	voter: &T::AccountId,
+	proposal_ids: &[T::ProposalId],
+	votes: u32,
+) -> Result<(), BenchmarkError> {
+	for proposal_id in proposal_ids {
+		Voting::<T>::vote(
+			RawOrigin::Signed(voter.clone()).into(),
+			votes.into(),
+			VoteKind::Aye,
+			*proposal_id,
+			None,
+		)?;
+	}
+	Ok(())
+}
+
+/// Casts "Aye" votes on each proposal from as many voters as the quorum of the track needs, each
+/// with enough convicted votes to reach the min participation of the track on its own, so the
+/// proposals pass and their deposits are refunded.
+fn vote_with_quorum<T: Config>(proposal_ids: &[T::ProposalId]) -> Result<(), BenchmarkError> {
+	let (_, track_info) = track::<T>();
+	let votes = track_info.min_participation.max(One::one());
+	for i in 0..track_info.min_voters.max(1) {
+		let voter = funded_voter::<T>("quorum", i);
+		for proposal_id in proposal_ids {
+			Voting::<T>::vote(
+				RawOrigin::Signed(voter.clone()).into(),
+				votes,
+				VoteKind::Aye,
+				*proposal_id,
+				Some(Conviction::Locked1x),
+			)?;
+		}
+	}
+	Ok(())
+}
+
+/// Checks that the proposal passed and its call was handed to the scheduler.
+fn assert_enacted<T: Config>(proposal_id: T::ProposalId) {
+	assert_eq!(
+		ProposalPool::<T>::get(proposal_id).map(|proposal| proposal.status),
+		Some(ProposalStatus::Enacted)
+	);
+	assert!(T::Scheduler::next_dispatch_time((ENACTMENT_ID, proposal_id).blake2_256()).is_ok());
+}
+
+/// Creates a voter with one vote on each of `count` open proposals.
+fn voter_with_open_votes<T: Config>(count: u32) -> Result<T::AccountId, BenchmarkError> {
+	let voter = funded_voter::<T>("target", 0);
+	let mut proposal_ids = Vec::new();
+	for _ in 0..count {
+		proposal_ids.push(create_proposal::<T>(&voter)?);
+	}
+	start_voting_period::<T>();
+	vote_on::<T>(&voter, &proposal_ids, 1)?;
+	Ok(voter)
+}
+
+/// A call to attach to proposals that are expected to pass.
+fn remark_call<T: Config>() -> Result<BoundedCallOf<T>, BenchmarkError> {
+	let call: CallOf<T> = frame_system::Call::<T>::remark { remark: vec![] }.into();
+	Ok(T::Preimages::bound(call)?)
+}
+
+/// Creates `count` proposals that will pass, each with a call to enact, and returns their ids.
+fn passing_proposals<T: Config>(count: u32) -> Result<Vec<T::ProposalId>, BenchmarkError> {
+	let mut proposal_ids = Vec::new();
+	for i in 0..count {
+		proposal_ids.push(ProposalIndex::<T>::get());
+		Voting::<T>::make_proposal(
+			RawOrigin::Signed(funded_voter::<T>("proposer", i)).into(),
+			track::<T>().0,
+			metadata::<T>(32),
+			Some(remark_call::<T>()?),
+		)?;
+	}
+	start_voting_period::<T>();
+	vote_with_quorum::<T>(&proposal_ids)?;
+	Ok(proposal_ids)
+}
+
+/// Creates a ballot round of `count` proposals, each with a call to enact, with enough voice
+/// credits to cast one vote on every proposal, and moves the chain to the start of their voting
+/// period. Returns the round id.
+fn ballot_round<T: Config>(count: u32) -> Result<RoundId, BenchmarkError> {
+	let origin = T::RoundOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+	let proposer = funded_voter::<T>("proposer", 0);
+	let mut proposals = Vec::new();
+	for _ in 0..count {
+		proposals.push(ProposalIndex::<T>::get());
+		Voting::<T>::make_proposal(
+			RawOrigin::Signed(proposer.clone()).into(),
+			track::<T>().0,
+			metadata::<T>(32),
+			Some(remark_call::<T>()?),
+		)?;
+	}
+	start_voting_period::<T>();
+	let round = NextRoundId::<T>::get();
+	Voting::<T>::create_round(origin, proposals, count.into(), track::<T>().1.duration)?;
+	Ok(round)
+}
+
+/// Moves the chain to the start of the voting period of proposals created at the current block.
+fn start_voting_period<T: Config>() {
+	let start = frame_system::Pallet::<T>::block_number() + track::<T>().1.start_delay;
+	frame_system::Pallet::<T>::set_block_number(start);
+}
+
+/// Moves the chain past the voting period of proposals whose voting period starts at the current
+/// block.
+fn skip_voting_period<T: Config>() {
+	let end = frame_system::Pallet::<T>::block_number() + track::<T>().1.duration;
+	frame_system::Pallet::<T>::set_block_number(end + One::one());
+}
 
 #[benchmarks]
 mod benchmarks {
 	use super::*;
 
+	// Worst case: a registrar account uses its quota.
+	#[benchmark]
+	fn register_voters() {
+		let registrar: T::AccountId = account("registrar", 0, SEED);
+		Registrars::<T>::insert(&registrar, 1);
+		let voter: T::AccountId = account("voter", 0, SEED);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(registrar.clone()), voter.clone());
+
+		assert!(RegisteredAccounts::<T>::contains_key(&voter));
+		assert_eq!(Registrars::<T>::get(&registrar), Some(0));
+	}
+
+	#[benchmark]
+	fn register_self() {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		T::IdentityProvider::ensure_verified(&caller);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()));
+
+		assert_eq!(RegisteredAccounts::<T>::get(&caller), Some(RegistrationKind::Identity));
+	}
+
+	#[benchmark]
+	fn make_proposal(
+		d: Linear<0, { T::MaxDescriptionLength::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let caller = funded_voter::<T>("caller", 0);
+		let call = Some(remark_call::<T>()?);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), track::<T>().0, metadata::<T>(d), call);
+
+		assert!(ProposalPool::<T>::contains_key(T::ProposalId::default()));
+		Ok(())
+	}
+
+	// Worst case: the voter already voted on this proposal and has `h` other votes in the
+	// history, so the old vote is removed, the freeze recalculated and the new one, split between
+	// ayes and nays, added.
+	#[benchmark]
+	fn vote(h: Linear<0, { T::MaxVotes::get() - 1 }>) -> Result<(), BenchmarkError> {
+		let caller = funded_voter::<T>("caller", 0);
+		let mut proposal_ids = Vec::new();
+		for _ in 0..=h {
+			proposal_ids.push(create_proposal::<T>(&caller)?);
+		}
+		start_voting_period::<T>();
+		vote_on::<T>(&caller, &proposal_ids, 1)?;
+		let proposal_id = proposal_ids[h as usize];
+		let kind = VoteKind::Split { aye: 60u32.into(), nay: 40u32.into() };
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), 100u32.into(), kind, proposal_id, None);
+
+		assert_eq!(
+			VotingHistory::<T>::get(&caller).map(|history| history.len()),
+			Some(h as usize + 1)
+		);
+		Ok(())
+	}
+
+	// Worst case: the proposal passes and its call is handed to the scheduler.
+	#[benchmark]
+	fn end_vote() -> Result<(), BenchmarkError> {
+		let caller = funded_voter::<T>("caller", 0);
+		let proposal_id = ProposalIndex::<T>::get();
+		Voting::<T>::make_proposal(
+			RawOrigin::Signed(caller.clone()).into(),
+			track::<T>().0,
+			metadata::<T>(32),
+			Some(remark_call::<T>()?),
+		)?;
+		start_voting_period::<T>();
+		vote_with_quorum::<T>(&[proposal_id])?;
+		skip_voting_period::<T>();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), proposal_id);
+
+		assert_enacted::<T>(proposal_id);
+		Ok(())
+	}
+
+	// Worst case: the claimed proposal holds the highest freeze so the freeze has to be
+	// recalculated from the remaining `h - 1` votes.
+	#[benchmark]
+	fn claim_frozen_tokens(h: Linear<1, { T::MaxVotes::get() }>) -> Result<(), BenchmarkError> {
+		let caller = funded_voter::<T>("caller", 0);
+		let mut proposal_ids = Vec::new();
+		for _ in 0..h {
+			proposal_ids.push(create_proposal::<T>(&caller)?);
+		}
+		let proposal_id = proposal_ids.pop().ok_or(BenchmarkError::Weightless)?;
+		start_voting_period::<T>();
+		vote_on::<T>(&caller, &proposal_ids, 1)?;
+		vote_on::<T>(&caller, &[proposal_id], 10)?;
+		skip_voting_period::<T>();
+		Voting::<T>::end_vote(RawOrigin::Signed(caller.clone()).into(), proposal_id)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), proposal_id);
+
+		assert_eq!(
+			VotingHistory::<T>::get(&caller).map(|history| history.len()),
+			Some(h as usize - 1)
+		);
+		Ok(())
+	}
+
+	// Worst case: all `p` proposals ending in this block pass.
+	#[benchmark]
+	fn on_initialize(
+		p: Linear<0, { T::MaxAutoClosePerBlock::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let proposal_ids = passing_proposals::<T>(p)?;
+		let end = frame_system::Pallet::<T>::block_number() + track::<T>().1.duration;
+
+		#[block]
+		{
+			Voting::<T>::on_initialize(end);
+		}
+
+		for proposal_id in proposal_ids {
+			assert_enacted::<T>(proposal_id);
+		}
+		Ok(())
+	}
+
+	// Worst case: all `p` queued proposals pass.
+	#[benchmark]
+	fn on_idle(
+		p: Linear<0, { T::MaxProposalsEndingPerBlock::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let proposal_ids = passing_proposals::<T>(p)?;
+		OverdueProposals::<T>::put(BoundedVec::truncate_from(proposal_ids.clone()));
+		skip_voting_period::<T>();
+		let now = frame_system::Pallet::<T>::block_number();
+
+		#[block]
+		{
+			Voting::<T>::on_idle(now, Weight::MAX);
+		}
+
+		assert!(OverdueProposals::<T>::get().is_empty());
+		for proposal_id in proposal_ids {
+			assert_enacted::<T>(proposal_id);
+		}
+		Ok(())
+	}
+
+	// Worst case: every vote of the voter is on an open proposal and has to be removed.
 	#[benchmark]
-	fn do_something() {
-		let value = 100u32.into();
-		let caller: T::AccountId = whitelisted_caller();
+	fn deregister_voter(h: Linear<0, { T::MaxVotes::get() }>) -> Result<(), BenchmarkError> {
+		let origin =
+			T::RegistrarOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let voter = funded_voter::<T>("voter", 0);
+		let mut proposal_ids = Vec::new();
+		for _ in 0..h {
+			proposal_ids.push(create_proposal::<T>(&voter)?);
+		}
+		start_voting_period::<T>();
+		vote_on::<T>(&voter, &proposal_ids, 1)?;
+
 		#[extrinsic_call]
-		do_something(RawOrigin::Signed(caller), value);
+		_(origin as T::RuntimeOrigin, voter.clone());
 
-		assert_eq!(Something::<T>::get(), Some(value));
+		assert!(!RegisteredAccounts::<T>::contains_key(&voter));
+		assert!(!VotingHistory::<T>::contains_key(&voter));
+		Ok(())
 	}
 
 	#[benchmark]
-	fn cause_error() {
-		Something::<T>::put(100u32);
-		let caller: T::AccountId = whitelisted_caller();
+	fn suspend_voter() -> Result<(), BenchmarkError> {
+		let origin =
+			T::RegistrarOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let voter = funded_voter::<T>("voter", 0);
+		let until = frame_system::Pallet::<T>::block_number() + One::one();
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, voter.clone(), until);
+
+		assert_eq!(SuspendedUntil::<T>::get(&voter), Some(until));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_registration_expiry() -> Result<(), BenchmarkError> {
+		let origin =
+			T::RegistrarOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let voter = funded_voter::<T>("voter", 0);
+		let expiry = frame_system::Pallet::<T>::block_number() + One::one();
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, voter.clone(), Some(expiry));
+
+		assert_eq!(RegistrationExpiry::<T>::get(&voter), Some(expiry));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_registrar() {
+		let registrar: T::AccountId = account("registrar", 0, SEED);
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, registrar.clone(), Some(10));
+
+		assert_eq!(Registrars::<T>::get(&registrar), Some(10));
+	}
+
+	// Worst case: a registrar account registers `n` new voters.
+	#[benchmark]
+	fn register_voters_batch(n: Linear<0, { T::MaxRegistrationBatch::get() }>) {
+		let registrar: T::AccountId = account("registrar", 0, SEED);
+		Registrars::<T>::insert(&registrar, n);
+		let voters: Vec<T::AccountId> = (0..n).map(|i| account("voter", i, SEED)).collect();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(registrar.clone()), voters.clone());
+
+		for voter in voters {
+			assert!(RegisteredAccounts::<T>::contains_key(&voter));
+		}
+		assert_eq!(Registrars::<T>::get(&registrar), Some(0));
+	}
+
+	#[benchmark]
+	fn create_round(
+		p: Linear<1, { T::MaxProposalsPerRound::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let origin =
+			T::RoundOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let proposer = funded_voter::<T>("proposer", 0);
+		let mut proposals = Vec::new();
+		for _ in 0..p {
+			proposals.push(create_proposal::<T>(&proposer)?);
+		}
+		let duration = track::<T>().1.duration;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, proposals, p.into(), duration);
+
+		assert!(Rounds::<T>::contains_key(0));
+		Ok(())
+	}
+
+	// Worst case: the ballot votes on every proposal of the round.
+	#[benchmark]
+	fn cast_ballot(p: Linear<1, { T::MaxProposalsPerRound::get() }>) -> Result<(), BenchmarkError> {
+		let round = ballot_round::<T>(p)?;
+		let caller = funded_voter::<T>("caller", 0);
+		let votes = Rounds::<T>::get(round)
+			.ok_or(BenchmarkError::Weightless)?
+			.proposals
+			.into_iter()
+			.map(|proposal_id| (proposal_id, true, 1u32.into()))
+			.collect::<Vec<_>>();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), round, votes);
+
+		assert!(Ballots::<T>::contains_key(round, &caller));
+		Ok(())
+	}
+
+	// Worst case: every proposal of the round passes and has a call to schedule.
+	#[benchmark]
+	fn close_round(p: Linear<1, { T::MaxProposalsPerRound::get() }>) -> Result<(), BenchmarkError> {
+		let round = ballot_round::<T>(p)?;
+		let caller = funded_voter::<T>("caller", 0);
+		let votes = Rounds::<T>::get(round)
+			.ok_or(BenchmarkError::Weightless)?
+			.proposals
+			.into_iter()
+			.map(|proposal_id| (proposal_id, true, 1u32.into()))
+			.collect::<Vec<_>>();
+		for i in 0..track::<T>().1.min_voters.max(1) {
+			let voter = funded_voter::<T>("quorum", i);
+			Voting::<T>::cast_ballot(RawOrigin::Signed(voter).into(), round, votes.clone())?;
+		}
+		skip_voting_period::<T>();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), round);
+
+		assert!(Rounds::<T>::get(round).map_or(false, |ballot_round| ballot_round.closed));
+		for (proposal_id, _, _) in votes {
+			assert_enacted::<T>(proposal_id);
+		}
+		Ok(())
+	}
+
+	#[benchmark]
+	fn kill_proposal() -> Result<(), BenchmarkError> {
+		let origin =
+			T::CancelOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let proposer = funded_voter::<T>("proposer", 0);
+		let proposal_id = create_proposal::<T>(&proposer)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, proposal_id);
+
+		assert!(!ProposalDeposits::<T>::contains_key(proposal_id));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn withdraw_proposal() -> Result<(), BenchmarkError> {
+		let proposer = funded_voter::<T>("proposer", 0);
+		let proposal_id = create_proposal::<T>(&proposer)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(proposer), proposal_id);
+
+		assert!(ProposalPool::<T>::get(proposal_id)
+			.map_or(false, |proposal| proposal.status == ProposalStatus::Cancelled));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn cancel_proposal() -> Result<(), BenchmarkError> {
+		let origin =
+			T::CancelOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let proposer = funded_voter::<T>("proposer", 0);
+		let proposal_id = create_proposal::<T>(&proposer)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, proposal_id);
+
+		assert!(ProposalPool::<T>::get(proposal_id)
+			.map_or(false, |proposal| proposal.status == ProposalStatus::Cancelled));
+		Ok(())
+	}
+
+	// Worst case: the target voted on `h` open proposals, which all get the delegated votes.
+	#[benchmark]
+	fn delegate(h: Linear<0, { T::MaxVotes::get() }>) -> Result<(), BenchmarkError> {
+		let caller = funded_voter::<T>("caller", 0);
+		let target = voter_with_open_votes::<T>(h)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), target, Conviction::Locked6x, 100u32.into());
+
+		assert!(Delegations::<T>::contains_key(&caller));
+		Ok(())
+	}
+
+	// Worst case: the target voted on `h` open proposals, which all lose the delegated votes,
+	// and the tokens stay locked.
+	#[benchmark]
+	fn undelegate(h: Linear<0, { T::MaxVotes::get() }>) -> Result<(), BenchmarkError> {
+		let caller = funded_voter::<T>("caller", 0);
+		let target = voter_with_open_votes::<T>(h)?;
+		Voting::<T>::delegate(
+			RawOrigin::Signed(caller.clone()).into(),
+			target,
+			Conviction::Locked6x,
+			100u32.into(),
+		)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()));
+
+		assert!(!Delegations::<T>::contains_key(&caller));
+		Ok(())
+	}
+
+	// Worst case: the tokens of the removed delegation are released.
+	#[benchmark]
+	fn unlock_delegation() -> Result<(), BenchmarkError> {
+		let caller = funded_voter::<T>("caller", 0);
+		let target = funded_voter::<T>("target", 0);
+		Voting::<T>::delegate(
+			RawOrigin::Signed(caller.clone()).into(),
+			target,
+			Conviction::Locked6x,
+			100u32.into(),
+		)?;
+		Voting::<T>::undelegate(RawOrigin::Signed(caller.clone()).into())?;
+		let unlock_at = DelegationLocks::<T>::get(&caller).ok_or(BenchmarkError::Weightless)?.0;
+		frame_system::Pallet::<T>::set_block_number(unlock_at);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()));
+
+		assert!(!DelegationLocks::<T>::contains_key(&caller));
+		Ok(())
+	}
+
+	// Worst case: all `h` votes of the account are claimed, from another account.
+	#[benchmark]
+	fn unlock_all(h: Linear<1, { T::MaxVotes::get() }>) -> Result<(), BenchmarkError> {
+		let caller = funded_voter::<T>("caller", 0);
+		let voter = funded_voter::<T>("voter", 0);
+		let mut proposal_ids = Vec::new();
+		for _ in 0..h {
+			proposal_ids.push(create_proposal::<T>(&voter)?);
+		}
+		start_voting_period::<T>();
+		vote_on::<T>(&voter, &proposal_ids, 10)?;
+		skip_voting_period::<T>();
+		for proposal_id in proposal_ids {
+			Voting::<T>::end_vote(RawOrigin::Signed(caller.clone()).into(), proposal_id)?;
+		}
+
 		#[extrinsic_call]
-		cause_error(RawOrigin::Signed(caller));
+		_(RawOrigin::Signed(caller), voter.clone());
 
-		assert_eq!(Something::<T>::get(), Some(101u32));
+		assert!(!VotingHistory::<T>::contains_key(&voter));
+		Ok(())
 	}
 
 	impl_benchmark_test_suite!(Voting, crate::mock::new_test_ext(), crate::mock::Test);
@@ -0,0 +1,68 @@
+//! Convictions multiplying the votes of a voter in exchange for a longer lock.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::sp_runtime::traits::{AtLeast32BitUnsigned, Saturating};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// The tallies count tenths of a vote, so that votes counted at 0.1x are never rounded down.
+pub const TALLY_SCALE: u32 = 10;
+
+/// How strongly a voter believes in their vote. A higher conviction multiplies the votes, but
+/// the tokens of a winning vote stay frozen for more lock periods after the proposal ends.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Conviction {
+	/// 0.1x votes, no lock.
+	#[codec(index = 0)]
+	None,
+	/// 1x votes, locked for 1 lock period.
+	#[codec(index = 1)]
+	Locked1x,
+	/// 2x votes, locked for 2 lock periods.
+	#[codec(index = 2)]
+	Locked2x,
+	/// 3x votes, locked for 4 lock periods.
+	#[codec(index = 3)]
+	Locked3x,
+	/// 4x votes, locked for 8 lock periods.
+	#[codec(index = 4)]
+	Locked4x,
+	/// 5x votes, locked for 16 lock periods.
+	#[codec(index = 5)]
+	Locked5x,
+	/// 6x votes, locked for 32 lock periods.
+	#[codec(index = 6)]
+	Locked6x,
+}
+
+impl Conviction {
+	/// The number of lock periods the tokens of a winning vote stay frozen for.
+	pub fn lock_periods(&self) -> u32 {
+		match self {
+			Self::None => 0,
+			Self::Locked1x => 1,
+			Self::Locked2x => 2,
+			Self::Locked3x => 4,
+			Self::Locked4x => 8,
+			Self::Locked5x => 16,
+			Self::Locked6x => 32,
+		}
+	}
+
+	/// The votes counted in the tally for `votes` cast with this conviction, in tenths of a
+	/// vote (see [`TALLY_SCALE`]).
+	pub fn votes<Balance: AtLeast32BitUnsigned>(&self, votes: Balance) -> Balance {
+		let tenths: u32 = match self {
+			Self::None => 1,
+			Self::Locked1x => 10,
+			Self::Locked2x => 20,
+			Self::Locked3x => 30,
+			Self::Locked4x => 40,
+			Self::Locked5x => 50,
+			Self::Locked6x => 60,
+		};
+		votes.saturating_mul(tenths.into())
+	}
+}
@@ -0,0 +1,120 @@
+//! Identity checks used to let accounts register themselves as voters.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use core::marker::PhantomData;
+use frame_support::traits::Get;
+use pallet_identity::Judgement;
+use scale_info::TypeInfo;
+
+/// Decides whether the identity of an account is good enough to vote.
+pub trait IdentityProvider<AccountId> {
+	/// Returns true if the account currently has a sufficient identity.
+	fn has_identity(who: &AccountId) -> bool;
+
+	/// Gives the account a sufficient identity, so benchmarks can register it.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn ensure_verified(who: &AccountId);
+}
+
+/// No account has a sufficient identity, so only manual registration is possible.
+impl<AccountId> IdentityProvider<AccountId> for () {
+	fn has_identity(_who: &AccountId) -> bool {
+		false
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn ensure_verified(_who: &AccountId) {}
+}
+
+/// The lowest registrar judgement an identity needs to be considered sufficient.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug, MaxEncodedLen, TypeInfo)]
+pub enum JudgementLevel {
+	/// `Reasonable` and `KnownGood` judgements are accepted.
+	Reasonable,
+	/// Only `KnownGood` judgements are accepted.
+	KnownGood,
+}
+
+impl JudgementLevel {
+	/// Whether the judgement meets this level.
+	pub fn is_met_by<Balance>(&self, judgement: &Judgement<Balance>) -> bool {
+		match self {
+			Self::Reasonable => matches!(judgement, Judgement::Reasonable | Judgement::KnownGood),
+			Self::KnownGood => matches!(judgement, Judgement::KnownGood),
+		}
+	}
+}
+
+/// Adapter accepting accounts that have at least one `pallet_identity` registrar judgement
+/// meeting `Threshold`.
+///
+/// Judgements are checked every time they are needed, so once a registrar revokes or downgrades
+/// the judgement the account can't be used anymore.
+pub struct IdentityJudgement<T, Threshold>(PhantomData<(T, Threshold)>);
+
+impl<T, Threshold> IdentityProvider<T::AccountId> for IdentityJudgement<T, Threshold>
+where
+	T: pallet_identity::Config,
+	Threshold: Get<JudgementLevel>,
+{
+	fn has_identity(who: &T::AccountId) -> bool {
+		let threshold = Threshold::get();
+		pallet_identity::Pallet::<T>::identity(who).map_or(false, |registration| {
+			registration
+				.judgements
+				.iter()
+				.any(|(_, judgement)| threshold.is_met_by(judgement))
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn ensure_verified(who: &T::AccountId) {
+		benchmarking::set_known_good::<T>(who);
+	}
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking {
+	use super::*;
+	use frame_support::{
+		sp_runtime::traits::Zero, storage_alias, traits::Currency, BoundedVec, Twox64Concat,
+	};
+	use pallet_identity::{Data, IdentityInfo, Registration};
+
+	type BalanceOf<T> = <<T as pallet_identity::Config>::Currency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::Balance;
+
+	#[storage_alias]
+	type IdentityOf<T: pallet_identity::Config> = StorageMap<
+		pallet_identity::Pallet<T>,
+		Twox64Concat,
+		<T as frame_system::Config>::AccountId,
+		Registration<
+			BalanceOf<T>,
+			<T as pallet_identity::Config>::MaxRegistrars,
+			<T as pallet_identity::Config>::MaxAdditionalFields,
+		>,
+	>;
+
+	/// Writes an identity with a `KnownGood` judgement, which meets every threshold.
+	pub fn set_known_good<T: pallet_identity::Config>(who: &T::AccountId) {
+		let info = IdentityInfo {
+			additional: BoundedVec::default(),
+			display: Data::None,
+			legal: Data::None,
+			web: Data::None,
+			riot: Data::None,
+			email: Data::None,
+			pgp_fingerprint: None,
+			image: Data::None,
+			twitter: Data::None,
+		};
+		let registration = Registration {
+			judgements: BoundedVec::truncate_from(sp_std::vec![(0, Judgement::KnownGood)]),
+			deposit: Zero::zero(),
+			info,
+		};
+		IdentityOf::<T>::insert(who, registration);
+	}
+}
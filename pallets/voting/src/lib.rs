@@ -11,21 +11,80 @@
 //! There is one pool that the proposals are added into that the voter can choose
 //! to vote on.
 //!
-//! The proposals have a configurable duration that starts from the moment the
-//! proposal is created and is counted in block numbers. It has to be manually closed.
+//! Every proposal is made on a track. Tracks (for example for treasury spends, runtime upgrades
+//! or signalling) are configured in the runtime and each has its own start delay, voting period
+//! duration, participation threshold, quorum, approval threshold and deposit, counted in block
+//! numbers from the moment the proposal is created. Proposals are closed automatically at the
+//! beginning of the block their voting period ends in. Only a limited number of proposals are
+//! closed per block, the rest are queued and closed when the block has spare weight. Anyone can
+//! still close a proposal manually.
 //!
 //! The voters will vote in approval ("Aye") or rejection ("Nay"), choosing how many votes
-//! they want to add to their choice and locking the square of the votes as tokens.
+//! they want to add to their choice and locking the square of the votes as tokens. Voters can also
+//! abstain, which counts for the turnout and the quorum but not for the approval, or split their
+//! votes between ayes and nays. The tokens locked are the square of all the votes, however they
+//! are split.
 //!
-//! The voters have the chance to unlock their tokens after the proposal has been closed.
+//! The [`LockMode`](lock_mode::LockMode) of the runtime decides how the votes of an account add
+//! up. With `OverlappingFreeze` the votes freeze the tokens and the freezes overlap, so the tokens
+//! of the most expensive vote back every other vote. With `CumulativeHold` the tokens of every vote
+//! are held and the holds add up.
+//!
+//! The voters have the chance to unlock their tokens after the proposal has been closed. A vote
+//! can be cast with a [`Conviction`](conviction::Conviction), from 0.1x to 6x, which multiplies
+//! its votes in the tally. In exchange, if the vote is on the winning side of the proposal, its
+//! tokens stay frozen for a number of `VoteLockingPeriod`s after the voting period ends, doubling
+//! with every step of conviction. Votes cast without a conviction, abstentions and split votes are
+//! counted at 0.1x and never locked, like `Conviction::None`. Tallies count tenths of a vote, so
+//! small votes at 0.1x still count.
+//!
+//! A registered voter can also delegate to another voter. The tokens delegated are frozen and
+//! their square root, multiplied by the conviction of the delegation, is added to every vote of
+//! the delegate on open proposals, current and future, on the side the delegate voted. Accounts
+//! with votes on open proposals can't delegate. Once a delegation is removed its tokens stay frozen
+//! for the lock periods of its conviction.
+//!
+//! A proposal passes when it has votes from at least the quorum of distinct voters of its track
+//! and its ayes reach the approval threshold of the track, for example 2/3 of the votes for a
+//! supermajority. Proposals below the quorum are closed without a result.
+//!
+//! Each track also picks a [`VoteThreshold`](vote_threshold::VoteThreshold) that biases the
+//! ayes a proposal needs by its turnout, the number of its voters relative to the number of
+//! registered voters. With `SuperMajorityApprove` a low turnout needs a supermajority of ayes, with
+//! `SuperMajorityAgainst` it needs a supermajority of nays to reject the proposal, and
+//! `SimpleMajority` only needs more ayes than nays.
+//!
+//! Every proposal has a [`ProposalStatus`]. It is `Pending` until the first vote and `Active`
+//! afterwards. When it closes, the status records the outcome of the vote (`Passed`, `Enacted` if
+//! its call was scheduled, `Rejected`, `Tied`, `NoQuorum`, or `Expired` without any votes) along
+//! with the block it was closed in. Withdrawn, cancelled and killed proposals are `Cancelled`.
+//!
+//! A proposal can optionally carry a call, either inline or as a reference to a noted preimage.
+//! If the proposal passes, the call is handed to the scheduler and dispatched with the configured
+//! enactment origin after the enactment period.
+//!
+//! Several proposals can be grouped into a ballot round. Every voter gets the same budget of
+//! voice credits for the round and casts a single ballot spreading votes over its proposals, where
+//! `n` votes cost `n * n` credits and count like votes cast without a conviction. The proposals of
+//! a round can only be voted on through ballots and are tallied and closed together when the round
+//! is closed.
+//!
+//! Making a proposal holds the deposit of its track from the proposer. The deposit is returned when
+//! the proposal closes with at least the min participation of the track, otherwise it is slashed,
+//! as it is when the cancel origin kills the proposal.
+//!
+//! The proposer can withdraw a proposal as long as it has no votes or during a short grace period
+//! after making it, and the cancel origin can cancel any open proposal. The deposit of withdrawn
+//! and cancelled proposals is refunded, their call is not enacted and voters can claim their frozen
+//! tokens right away.
 //!
 //! ### Terminology
 //!
-//! - **Lock Period:** A period of time after proposal enactment that the tokens of _winning_ voters
-//! will be locked.
+//! - **Lock Period:** A period of time after the voting period of a proposal ends that the tokens
+//! of _winning_ voters will be locked.
 //! - **Conviction:** An indication of a voter's strength of belief in their vote. An increase
-//! in conviction indicates that a token holder is willing to lock the square of their votes as
-//! tokens.
+//! in conviction multiplies the votes counted in the tally and indicates that a token holder is
+//! willing to lock the square of their votes as tokens for more lock periods.
 //! - **Vote:** A value that can either be in approval ("Aye") or rejection ("Nay") of a particular
 //! referendum.
 //! - **Proposal:** A submission to the chain that represents an action that a proposer (either an
@@ -40,34 +99,95 @@
 //! These calls can be made from any externally held account capable of creating
 //! a signed extrinsic.
 //!
-//! - 'end_vote' - Will end the vote if the time allocation has expired.
+//! - 'end_vote' - Will end the vote if the time allocation has expired. Fallback for proposals
+//!   that haven't been closed automatically yet.
+//! - `close_round` - Tallies and closes every proposal of a ballot round once the round is over.
+//!
+//! - `register_self` - Registers the caller as a voter if their on-chain identity meets the
+//!   configured requirements.
 //!
 //! #### Registered users
 //!
 //! These calls can only be made by an account that has been registered into the pool.
+//! Accounts that registered themselves through their identity can only make proposals and vote as
+//! long as their identity still meets the requirements.
 //!
-//! - `propose` - Submits a proposal, represented as a hash. Requires the a registered voter.
+//! - `propose` - Submits a proposal with a title, a description and an optional call to dispatch
+//!   if it passes. Requires the a registered voter. Long descriptions are noted as a preimage.
 //! - `vote` - Votes for a proposal, either the vote is "Aye" to enact the proposal or "Nay" to keep
 //!   the status quo. The number of votes scales quadratically with the tokens frozen as a deposit.
+//!   The votes can also abstain or be split between "Aye" and "Nay". Ayes and nays can be cast
+//!   with a conviction.
 //! - 'claim frozen tokens' The voter can claim the frozen tokens used for a proposal, after the
-//!   proposal ends.
+//!   proposal ends and, for winning votes cast with a conviction, after their lock periods.
+//! - `cast_ballot` - Votes on several proposals of a ballot round at once, within the voice credit
+//!   budget of the round.
+//! - `withdraw_proposal` - Withdraws a proposal of the caller that has no votes yet or whose
+//!   voting period started less than `WithdrawalPeriod` blocks ago. The deposit is refunded.
+//! - `delegate` - Lends the votes of an amount of tokens, with a conviction, to another voter.
+//! - `undelegate` - Removes the delegation of the caller and starts its lock periods.
+//! - `unlock_delegation` - Releases the tokens of removed delegations once their lock periods are
+//!   over.
+//!
+//! #### Round origin
+//!
+//! - `create_round` - Groups open proposals without votes into a ballot round.
 //!
 //! #### Root
 //!
-//! - 'register voters' - Registers an account into a pool of voters. Requires sudo.
+//! - `set_registrar` - Adds an account to the registrars with a quota of voters it can register,
+//!   or removes it.
+//!
+//! #### Registrar
+//!
+//! These calls can only be made by the configured `RegistrarOrigin`.
+//!
+//! - 'register voters' - Registers an account into a pool of voters. Can also be called by a
+//!   registrar account as long as it has quota left.
+//! - `deregister_voter` - Removes an account from the pool of voters. Its votes on proposals that
+//!   are still open are removed from the tallies and the tokens frozen for them are released.
+//! - `suspend_voter` - Stops an account from making proposals and voting until a given block. Its
+//!   votes are kept.
+//! - `set_registration_expiry` - Sets or clears the block from which the registration of an
+//!   account is no longer valid. Its votes are kept.
+//!
+//! #### Cancel origin
+//!
+//! These calls can only be made by the configured `CancelOrigin`.
+//!
+//! - `cancel_proposal` - Ends an open proposal without a result and refunds its deposit. Voters
+//!   can claim their frozen tokens right away.
+//! - `kill_proposal` - Ends an open proposal without a result and slashes its deposit. Voters can
+//!   claim their frozen tokens right away.
 
 #![cfg_attr(not(feature = "std"), no_std)]
+use conviction::{Conviction, TALLY_SCALE};
 use frame_support::{
 	dispatch::Vec,
 	pallet_prelude::*,
-	sp_runtime::traits::{AtLeast32BitUnsigned, CheckedAdd, CheckedMul, CheckedSub, Convert, Hash},
+	sp_runtime::{
+		traits::{
+			AtLeast32BitUnsigned, CheckedAdd, CheckedMul, CheckedSub, Convert, Hash,
+			IntegerSquareRoot, Saturating, Zero,
+		},
+		PerThing,
+	},
 	traits::{
 		fungible,
-		fungible::{InspectFreeze, MutateFreeze},
+		fungible::{InspectFreeze, MutateFreeze, MutateHold},
+		fungibles,
+		schedule::{v3::Named as ScheduleNamed, DispatchTime},
+		tokens::{Fortitude, Precision, Preservation},
+		Bounded, Imbalance, OnUnbalanced, OriginTrait, QueryPreimage, StorePreimage,
 	},
+	Hashable,
 };
-use frame_system::pallet_prelude::BlockNumberFor;
+use frame_system::pallet_prelude::{BlockNumberFor, OriginFor};
+use identity::IdentityProvider;
+use lock_mode::LockMode;
 pub use pallet::*;
+use sp_core::H256;
+use tracks::{TrackId, TracksInfo};
 
 #[cfg(test)]
 mod mock;
@@ -77,22 +197,49 @@ mod tests;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
+pub mod conviction;
+pub mod identity;
+pub mod lock_mode;
+pub mod migrations;
+pub mod tracks;
+mod types;
+pub mod vote_threshold;
+pub mod weights;
+pub use types::{ProposalInfo, ProposalStatus, Tally, VoteInfo, VoteKind};
+pub use weights::*;
 
 pub type BalanceOf<T> = <<T as Config>::NativeBalance as fungible::Inspect<
 	<T as frame_system::Config>::AccountId,
 >>::Balance;
+pub type AssetIdOf<T> =
+	<<T as Config>::Assets as fungibles::Inspect<<T as frame_system::Config>::AccountId>>::AssetId;
+pub type CallOf<T> = <T as frame_system::Config>::RuntimeCall;
+pub type BoundedCallOf<T> = Bounded<CallOf<T>>;
+pub type CreditOf<T> =
+	fungible::Credit<<T as frame_system::Config>::AccountId, <T as Config>::NativeBalance>;
+pub type PalletsOriginOf<T> =
+	<<T as frame_system::Config>::RuntimeOrigin as OriginTrait>::PalletsOrigin;
+
+/// The ballot round index type.
+pub type RoundId = u32;
+
+/// Prefix of the scheduler task names used to enact passed proposals.
+const ENACTMENT_ID: [u8; 6] = *b"voting";
 
 #[frame_support::pallet]
 pub mod pallet {
 	use crate::*;
-	use core::cmp::Ordering;
 	use frame_support::{
 		sp_runtime::traits::{One, Zero},
 		BoundedVec,
 	};
 	use frame_system::pallet_prelude::*;
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(13);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::config]
@@ -102,15 +249,25 @@ pub mod pallet {
 		/// The overarching freeze reason.
 		type RuntimeFreezeReason: From<FreezeReason>;
 
+		/// The overarching hold reason.
+		type RuntimeHoldReason: From<HoldReason>;
+
 		/// Type to access the Balances Pallet.
 		type NativeBalance: fungible::Inspect<Self::AccountId>
 			+ fungible::Mutate<Self::AccountId>
 			+ fungible::hold::Inspect<Self::AccountId>
 			+ fungible::hold::Mutate<Self::AccountId>
+			+ fungible::hold::Balanced<Self::AccountId>
+			+ fungible::hold::Inspect<Self::AccountId, Reason = Self::RuntimeHoldReason>
 			+ fungible::freeze::Inspect<Self::AccountId>
 			+ fungible::freeze::Mutate<Self::AccountId>
 			+ fungible::freeze::Mutate<Self::AccountId, Id = Self::RuntimeFreezeReason>;
 
+		/// Type to access the Assets Pallet, for tracks that vote with an asset instead of the
+		/// native token. The assets pallet has to use this pallet as its `Freezer` for the votes
+		/// to freeze the asset.
+		type Assets: fungibles::Inspect<Self::AccountId, Balance = BalanceOf<Self>>;
+
 		/// A helper to convert a block number to a balance type.
 		type BlockNumberToBalance: Convert<BlockNumberFor<Self>, BalanceOf<Self>>;
 
@@ -119,12 +276,14 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxVotes: Get<u32>;
 
-		/// Proposal duration measured in block numbers.
-		/// The proposal cannot be closed before this many blocks have been added since the proposal
-		/// started. The proposal can be closed at any time after that.
-		/// Configurable in the runtime config.
-		#[pallet::constant]
-		type ProposalDuration: Get<BlockNumberFor<Self>>;
+		/// The tracks proposals can be made on, each with its own voting period, participation
+		/// threshold, deposit and allowed calls.
+		type Tracks: TracksInfo<
+			BalanceOf<Self>,
+			BlockNumberFor<Self>,
+			AssetIdOf<Self>,
+			CallOf<Self>,
+		>;
 
 		/// The proposal index type.
 		/// The concrete type is configurable in the runtime config.
@@ -137,19 +296,174 @@ pub mod pallet {
 			+ Default
 			+ One
 			+ CheckedAdd;
+
+		/// Max length of the title of a proposal, in bytes.
+		/// Configurable in the runtime config.
+		#[pallet::constant]
+		type MaxTitleLength: Get<u32>;
+
+		/// Max length of a proposal description stored in the pallet, in bytes. Longer
+		/// descriptions have to be noted as a preimage.
+		/// Configurable in the runtime config.
+		#[pallet::constant]
+		type MaxDescriptionLength: Get<u32>;
+
+		/// The preimage provider used to look up the calls of proposals.
+		type Preimages: QueryPreimage + StorePreimage;
+
+		/// The scheduler used to dispatch the calls of passed proposals.
+		type Scheduler: ScheduleNamed<BlockNumberFor<Self>, CallOf<Self>, PalletsOriginOf<Self>>;
+
+		/// The origin the call of a passed proposal is dispatched with.
+		type EnactmentOrigin: Get<PalletsOriginOf<Self>>;
+
+		/// Max number of proposals whose voting period can end in the same block.
+		/// Also bounds the queue of proposals waiting to be closed when a block has spare weight.
+		/// Configurable in the runtime config.
+		#[pallet::constant]
+		type MaxProposalsEndingPerBlock: Get<u32>;
+
+		/// Max number of proposals closed in `on_initialize`. The remaining proposals ending in
+		/// that block are closed in `on_idle`.
+		/// Configurable in the runtime config.
+		#[pallet::constant]
+		type MaxAutoClosePerBlock: Get<u32>;
+
+		/// Max number of voters registered in one `register_voters_batch` call.
+		/// Configurable in the runtime config.
+		#[pallet::constant]
+		type MaxRegistrationBatch: Get<u32>;
+
+		/// The origin that can register, deregister and suspend voters and set registration
+		/// expiries, for example a council or a multisig. Registrar accounts added with
+		/// `set_registrar` can also register voters.
+		type RegistrarOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The origin that can create ballot rounds.
+		type RoundOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Max number of proposals in a ballot round.
+		/// Configurable in the runtime config.
+		#[pallet::constant]
+		type MaxProposalsPerRound: Get<u32>;
+
+		/// Handler for the slashed proposal deposits.
+		type Slash: OnUnbalanced<CreditOf<Self>>;
+
+		/// The origin that can cancel proposals, refunding their deposit, and kill spam
+		/// proposals, slashing their deposit.
+		type CancelOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Number of blocks after the voting period of a proposal starts during which its proposer
+		/// can withdraw it, even if it has votes. A proposal without votes can be withdrawn until
+		/// it closes.
+		/// Configurable in the runtime config.
+		#[pallet::constant]
+		type WithdrawalPeriod: Get<BlockNumberFor<Self>>;
+
+		/// Decides which accounts can register themselves through `register_self`.
+		type IdentityProvider: identity::IdentityProvider<Self::AccountId>;
+
+		/// Number of blocks between a proposal passing and its call being dispatched.
+		/// Configurable in the runtime config.
+		#[pallet::constant]
+		type EnactmentPeriod: Get<BlockNumberFor<Self>>;
+
+		/// Number of blocks in a lock period. The tokens of a winning vote stay frozen for as many
+		/// lock periods after the voting period ends as its conviction requires.
+		/// Configurable in the runtime config.
+		#[pallet::constant]
+		type VoteLockingPeriod: Get<BlockNumberFor<Self>>;
+
+		/// Whether the costs of the votes of an account overlap as freezes or add up as holds.
+		/// Changing it on a live chain keeps the tokens of an account locked the old way until its
+		/// votes next change or are claimed, which releases the old lock and locks them the new
+		/// way.
+		#[pallet::constant]
+		type LockMode: Get<LockMode>;
+
+		/// The units a vote is counted in, so votes cast with `vote_with_amount` keep a fraction
+		/// of their square root. Changing it on a live chain requires migrating the open tallies.
+		#[pallet::constant]
+		type VotePrecision: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	/// Information about a created proposal.
 	/// Ayes and nays are of type Balance because they represent the square root of a frozen amount
-	/// of tokens.
+	/// of tokens. They count tenths of `VotePrecision` units, so that votes at 0.1x are never
+	/// rounded down. Once the proposal is closed they hold the final tally.
 	#[derive(Encode, Decode, Clone, MaxEncodedLen, TypeInfo)]
 	#[scale_info(skip_type_params(T))]
 	pub struct Proposal<T: Config> {
 		pub description: T::Hash,
+		/// The track the proposal was made on.
+		pub track: TrackId,
+		/// The block the voting period starts in.
 		pub start_block: BlockNumberFor<T>,
+		/// The block the voting period ends in. The proposal can be closed from this block.
+		pub end_block: BlockNumberFor<T>,
 		pub ayes: BalanceOf<T>,
 		pub nays: BalanceOf<T>,
-		pub end: bool,
+		/// Votes counted for the turnout but neither in approval nor against the proposal.
+		pub abstain: BalanceOf<T>,
+		/// The number of distinct voters, counted for the quorum.
+		pub voters: u32,
+		pub status: ProposalStatus,
+		/// The block the proposal was closed or cancelled in.
+		pub closed_at: Option<BlockNumberFor<T>>,
+		/// The call to schedule if the proposal passes.
+		pub call: Option<BoundedCallOf<T>>,
+	}
+
+	/// Where the description of a proposal can be read.
+	#[derive(
+		Encode,
+		Decode,
+		CloneNoBound,
+		PartialEqNoBound,
+		EqNoBound,
+		RuntimeDebugNoBound,
+		MaxEncodedLen,
+		TypeInfo,
+	)]
+	#[scale_info(skip_type_params(T))]
+	pub enum ProposalDescription<T: Config> {
+		/// The description, stored in the pallet.
+		#[codec(index = 0)]
+		Inline(BoundedVec<u8, T::MaxDescriptionLength>),
+		/// A description noted with the preimage pallet by the proposer, who pays its deposit.
+		#[codec(index = 1)]
+		Preimage { hash: H256, len: u32 },
+	}
+
+	impl<T: Config> ProposalDescription<T> {
+		/// The length of the description in bytes.
+		pub fn length(&self) -> u32 {
+			match self {
+				Self::Inline(text) => text.len() as u32,
+				Self::Preimage { len, .. } => *len,
+			}
+		}
+	}
+
+	/// The title and description of a proposal.
+	#[derive(
+		Encode,
+		Decode,
+		CloneNoBound,
+		PartialEqNoBound,
+		EqNoBound,
+		RuntimeDebugNoBound,
+		MaxEncodedLen,
+		TypeInfo,
+	)]
+	#[scale_info(skip_type_params(T))]
+	pub struct ProposalMetadata<T: Config> {
+		pub title: BoundedVec<u8, T::MaxTitleLength>,
+		pub description: ProposalDescription<T>,
 	}
 
 	/// Information about a specific vote on a specific proposal from a voter.
@@ -157,20 +471,143 @@ pub mod pallet {
 	#[scale_info(skip_type_params(T))]
 	pub struct UserVoteInfo<T: Config> {
 		pub proposal_id: T::ProposalId,
-		pub aye: bool,
+		/// Encoded like the `aye` flag it replaced, so older votes decode as `Aye` or `Nay`.
+		pub kind: VoteKind<BalanceOf<T>>,
+		/// The votes in `VotePrecision` units.
+		pub votes: BalanceOf<T>,
+		/// The tokens locked for the vote.
+		pub amount: BalanceOf<T>,
+		/// The conviction of the vote. Votes cast without one are stored with `Conviction::None`.
+		pub conviction: Conviction,
+		/// The block from which the tokens can be claimed if the vote won.
+		pub unlock_at: BlockNumberFor<T>,
+		/// The asset frozen for the vote, `None` for the native token.
+		pub asset: Option<AssetIdOf<T>>,
+	}
+
+	impl<T: Config> UserVoteInfo<T> {
+		/// The votes counted in the tally, multiplied by the conviction, in tenths.
+		pub fn tallied_votes(&self) -> BalanceOf<T> {
+			self.conviction.votes(self.votes)
+		}
+	}
+
+	/// The votes an account delegates to another voter.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, MaxEncodedLen, TypeInfo)]
+	#[scale_info(skip_type_params(T))]
+	pub struct Delegation<T: Config> {
+		/// The voter the votes are delegated to.
+		pub target: T::AccountId,
+		pub conviction: Conviction,
+		/// The tokens frozen for the delegation.
+		pub balance: BalanceOf<T>,
+		/// The votes added to the votes of the target, the square root of the balance multiplied
+		/// by the conviction.
 		pub votes: BalanceOf<T>,
 	}
 
+	/// A group of proposals voted on with ballots sharing a voice credit budget.
+	#[derive(Encode, Decode, Clone, MaxEncodedLen, TypeInfo)]
+	#[scale_info(skip_type_params(T))]
+	pub struct BallotRound<T: Config> {
+		/// The proposals of the round.
+		pub proposals: BoundedVec<T::ProposalId, T::MaxProposalsPerRound>,
+		/// The voice credits every voter can spend in the round.
+		pub voice_credits: BalanceOf<T>,
+		/// The block from which the round can be closed.
+		pub end: BlockNumberFor<T>,
+		/// Whether the round has been closed.
+		pub closed: bool,
+	}
+
+	/// How an account was registered as a voter.
+	#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug, MaxEncodedLen, TypeInfo)]
+	pub enum RegistrationKind {
+		/// Registered by root. Shares its encoding with the `true` stored by earlier versions.
+		#[codec(index = 1)]
+		Manual,
+		/// Registered through its identity, which is checked again on every use.
+		#[codec(index = 2)]
+		Identity,
+	}
+
 	/// A reason for freezing funds.
 	#[pallet::composite_enum]
 	pub enum FreezeReason {
 		#[codec(index = 0)]
 		AccountDeposit,
+		#[codec(index = 1)]
+		Delegation,
+	}
+
+	/// A reason for holding funds.
+	#[pallet::composite_enum]
+	pub enum HoldReason {
+		#[codec(index = 0)]
+		ProposalDeposit,
+		/// The tokens held for the votes of an account in `LockMode::CumulativeHold`. Hold reasons
+		/// can't carry the proposal id, so a single hold sums the cost of every vote and
+		/// `VoteHolds` keeps the amount held for each proposal, which is released when the vote is
+		/// claimed.
+		#[codec(index = 1)]
+		Vote,
 	}
 
-	/// A map of all the accounts that have been registered to vote.
+	/// A map of all the accounts that have been registered to vote. Their number is the
+	/// electorate the turnout of a proposal is measured against.
+	#[pallet::storage]
+	pub type RegisteredAccounts<T: Config> =
+		CountedStorageMap<_, Blake2_128Concat, T::AccountId, RegistrationKind>;
+
+	/// The title and description of every proposal. The hash of the description is kept in the
+	/// proposal itself.
 	#[pallet::storage]
-	pub type RegisteredAccounts<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, bool>;
+	pub type ProposalMetadataOf<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::ProposalId, ProposalMetadata<T>>;
+
+	/// The proposer and deposit of every proposal whose deposit hasn't been refunded or slashed
+	/// yet. Proposals are recorded even if no deposit was held, so their proposer can withdraw
+	/// them.
+	#[pallet::storage]
+	pub type ProposalDeposits<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::ProposalId, (T::AccountId, BalanceOf<T>)>;
+
+	/// A map of all the ballot rounds.
+	#[pallet::storage]
+	pub type Rounds<T: Config> = StorageMap<_, Blake2_128Concat, RoundId, BallotRound<T>>;
+
+	/// The id of the next ballot round.
+	#[pallet::storage]
+	pub type NextRoundId<T: Config> = StorageValue<_, RoundId, ValueQuery>;
+
+	/// The ballot round a proposal belongs to.
+	#[pallet::storage]
+	pub type ProposalRound<T: Config> = StorageMap<_, Blake2_128Concat, T::ProposalId, RoundId>;
+
+	/// The voice credits spent by the ballot of a voter in a round.
+	#[pallet::storage]
+	pub type Ballots<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		RoundId,
+		Blake2_128Concat,
+		T::AccountId,
+		BalanceOf<T>,
+	>;
+
+	/// Accounts that can register voters, with the number of voters they can still register.
+	#[pallet::storage]
+	pub type Registrars<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, u32>;
+
+	/// Voters that can't make proposals or vote until the stored block.
+	#[pallet::storage]
+	pub type SuspendedUntil<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BlockNumberFor<T>>;
+
+	/// The block from which the registration of a voter is no longer valid.
+	#[pallet::storage]
+	pub type RegistrationExpiry<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BlockNumberFor<T>>;
 
 	/// A value that increments with the number of proposals created.
 	/// It holds the next available id.
@@ -187,13 +624,130 @@ pub mod pallet {
 	pub type VotingHistory<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::AccountId, BoundedVec<UserVoteInfo<T>, T::MaxVotes>>;
 
+	/// The delegation of every account that delegates its votes.
+	#[pallet::storage]
+	pub type Delegations<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, Delegation<T>>;
+
+	/// The block until which the tokens of the removed delegations of an account stay frozen,
+	/// with the largest balance they delegated.
+	#[pallet::storage]
+	pub type DelegationLocks<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, (BlockNumberFor<T>, BalanceOf<T>)>;
+
+	/// The votes delegated to every voter, added to each of its votes on open proposals.
+	#[pallet::storage]
+	pub type DelegatedVotes<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	/// The tokens of an asset frozen for the votes of an account, reported to the assets pallet.
+	#[pallet::storage]
+	pub type AssetFreezes<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		AssetIdOf<T>,
+		Blake2_128Concat,
+		T::AccountId,
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	/// The native tokens held for the vote of an account on a proposal in
+	/// `LockMode::CumulativeHold`.
+	#[pallet::storage]
+	pub type VoteHolds<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::ProposalId,
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	/// The proposals whose voting period ends in a given block.
+	#[pallet::storage]
+	pub type ProposalsEndingAt<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		BoundedVec<T::ProposalId, T::MaxProposalsEndingPerBlock>,
+		ValueQuery,
+	>;
+
+	/// Proposals that couldn't be closed in `on_initialize` and are waiting for `on_idle`. When
+	/// it is full, the proposals that couldn't be closed are kept in `ProposalsEndingAt` for the
+	/// next block.
+	#[pallet::storage]
+	pub type OverdueProposals<T: Config> =
+		StorageValue<_, BoundedVec<T::ProposalId, T::MaxProposalsEndingPerBlock>, ValueQuery>;
+
+	#[pallet::genesis_config]
+	#[derive(frame_support::DefaultNoBound)]
+	pub struct GenesisConfig<T: Config> {
+		/// Accounts registered as voters.
+		pub voters: Vec<T::AccountId>,
+		/// Descriptions of the proposals made in the first block, on the first track. They are
+		/// stored in the pallet without a title.
+		pub proposals: Vec<Vec<u8>>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+		fn build(&self) {
+			for voter in &self.voters {
+				assert!(
+					!RegisteredAccounts::<T>::contains_key(voter),
+					"Voters in the genesis config must be unique"
+				);
+				RegisteredAccounts::<T>::insert(voter, RegistrationKind::Manual);
+			}
+			for description in &self.proposals {
+				let description = description
+					.clone()
+					.try_into()
+					.expect("Proposal descriptions in the genesis config must fit in the pallet");
+				let metadata = ProposalMetadata {
+					title: BoundedVec::default(),
+					description: ProposalDescription::Inline(description),
+				};
+				let (track, _) = T::Tracks::tracks()
+					.into_iter()
+					.next()
+					.expect("Proposals in the genesis config need a track");
+				Pallet::<T>::do_make_proposal(track, metadata, None)
+					.expect("Proposals in the genesis config must be valid");
+			}
+		}
+	}
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		/// Voter registered.
 		VoterRegistered { voter: T::AccountId },
+		/// A batch of voters was registered. `skipped` accounts were already registered or over
+		/// the quota of the registrar.
+		VotersBatchRegistered { registered: u32, skipped: Vec<T::AccountId> },
+		/// Ballot round created with the given proposals.
+		RoundCreated { round: RoundId, proposals: Vec<T::ProposalId> },
+		/// Ballot cast in a round, spending `cost` voice credits.
+		BallotCast { round: RoundId, who: T::AccountId, cost: BalanceOf<T> },
+		/// Ballot round tallied and closed.
+		RoundClosed { round: RoundId },
+		/// Registrar added with `quota` voters left to register, or removed if `None`.
+		RegistrarSet { registrar: T::AccountId, quota: Option<u32> },
+		/// Voter deregistered. `votes_removed` votes on open proposals were removed.
+		VoterDeregistered { voter: T::AccountId, votes_removed: u32 },
+		/// Voter can't make proposals or vote until block `until`.
+		VoterSuspended { voter: T::AccountId, until: BlockNumberFor<T> },
+		/// The suspension of the voter was lifted.
+		VoterReinstated { voter: T::AccountId },
+		/// The registration of the voter expires at block `expiry`, or never if `None`.
+		RegistrationExpirySet { voter: T::AccountId, expiry: Option<BlockNumberFor<T>> },
 		/// Proposal was successfully created.
-		ProposalCreated { proposal_id: T::ProposalId },
+		/// The hash of the description lets indexers verify the text they show.
+		ProposalCreated { proposal_id: T::ProposalId, description_hash: T::Hash },
 		/// Vote successfully added.
 		VoteAddedTo { proposal_id: T::ProposalId, votes: BalanceOf<T> },
 		/// Vote has finished. Proposal was accepted by the community.
@@ -202,12 +756,36 @@ pub mod pallet {
 		ProposalResultNay { proposal_id: T::ProposalId },
 		/// Vote has finished but it is a tie.
 		ProposalResultTie { proposal_id: T::ProposalId },
-		/// Tokens have been unlocked.
-		TokensUnlocked,
-		/// Amount of tokens frozen for this proposal is smaller than the max frozen amount.
-		NoTokensUnlocked,
+		/// Vote has finished without votes from enough voters to reach the quorum of the track.
+		ProposalResultNoQuorum { proposal_id: T::ProposalId },
+		/// `amount_released` tokens have been unlocked. `still_frozen` is the amount that stays
+		/// frozen for the other votes of the account.
+		TokensUnlocked {
+			who: T::AccountId,
+			amount_released: BalanceOf<T>,
+			still_frozen: BalanceOf<T>,
+		},
+		/// Amount of tokens frozen for this proposal is not higher than the amount frozen for the
+		/// other votes of the account, so the frozen amount is unchanged.
+		NoTokensUnlocked { who: T::AccountId, proposal_id: T::ProposalId, frozen: BalanceOf<T> },
 		/// Vote removed from the proposal by specifiying a zero amount of votes.
 		VoteRemovedOrCancelled { proposal_id: T::ProposalId },
+		/// The call of a passed proposal has been handed to the scheduler.
+		ProposalEnacted { proposal_id: T::ProposalId, result: DispatchResult },
+		/// Proposal killed as spam.
+		ProposalKilled { proposal_id: T::ProposalId },
+		/// Proposal withdrawn by its proposer.
+		ProposalWithdrawn { proposal_id: T::ProposalId },
+		/// Proposal cancelled by the cancel origin.
+		ProposalCancelled { proposal_id: T::ProposalId },
+		/// Proposal deposit released to the proposer.
+		DepositRefunded { proposal_id: T::ProposalId, who: T::AccountId, amount: BalanceOf<T> },
+		/// Proposal deposit slashed.
+		DepositSlashed { proposal_id: T::ProposalId, who: T::AccountId, amount: BalanceOf<T> },
+		/// `votes` votes delegated to `target`.
+		Delegated { who: T::AccountId, target: T::AccountId, votes: BalanceOf<T> },
+		/// Delegation to `target` removed.
+		Undelegated { who: T::AccountId, target: T::AccountId },
 	}
 
 	#[pallet::error]
@@ -232,26 +810,249 @@ pub mod pallet {
 		VoterAlreadyRegistered,
 		/// No votes from this account found for the specified proposal.
 		NoVotes,
+		/// Too many proposals already end in the same block.
+		TooManyProposalsEnding,
+		/// The identity of the account doesn't meet the requirements to vote.
+		InsufficientIdentity,
+		/// The voter is suspended.
+		VoterSuspended,
+		/// The registration of the voter has expired.
+		RegistrationExpired,
+		/// The registrar has no quota left to register voters.
+		RegistrarQuotaExhausted,
+		/// More voters than `MaxRegistrationBatch` in one batch.
+		TooManyVotersInBatch,
+		/// The preimage of the proposal description hasn't been noted or has another length.
+		DescriptionUnavailable,
+		/// Insufficient funds to pay the proposal deposit.
+		InsufficientDepositFunds,
+		/// No ballot round with the provided ID exists.
+		RoundDoesNotExist,
+		/// More proposals than `MaxProposalsPerRound` in one round or ballot.
+		TooManyProposalsInRound,
+		/// The proposal is ended, has votes or already belongs to a ballot round.
+		ProposalNotEligibleForRound,
+		/// The proposal belongs to a ballot round and can only be voted on and closed with it.
+		ProposalInRound,
+		/// The proposal doesn't belong to this ballot round.
+		ProposalNotInRound,
+		/// The same proposal appears more than once.
+		DuplicateProposal,
+		/// The ballot costs more voice credits than the round allows.
+		InsufficientVoiceCredits,
+		/// The voter already cast a ballot in this round.
+		BallotAlreadyCast,
+		/// The ballot round is over.
+		RoundOver,
+		/// The ballot round is not over yet.
+		RoundNotOver,
+		/// The ballot round has already been closed.
+		RoundAlreadyClosed,
+		/// Only the proposer can withdraw a proposal.
+		NotProposer,
+		/// The proposal has votes and its voting period started more than `WithdrawalPeriod`
+		/// blocks ago.
+		WithdrawalPeriodOver,
+		/// No track with the provided ID exists.
+		UnknownTrack,
+		/// The voting period of the proposal hasn't started yet.
+		VotingNotStarted,
+		/// The ayes and nays of a split vote don't add up to the votes, or a split vote was cast
+		/// with `vote_with_amount`.
+		InvalidSplit,
+		/// Abstentions and split votes can't be cast with a conviction.
+		InvalidConviction,
+		/// The vote won and its conviction still locks the tokens.
+		VoteLocked,
+		/// The account already delegates its votes.
+		AlreadyDelegating,
+		/// The account doesn't delegate its votes.
+		NotDelegating,
+		/// The conviction of a removed delegation still locks its tokens.
+		DelegationLocked,
+		/// Votes can only be delegated to another registered voter.
+		InvalidDelegate,
+		/// The account owns enough tokens, but some of them are held or needed for its existential
+		/// deposit and can't be frozen. Errors can't carry the balance, so `vote_shortfall` returns
+		/// the tokens missing for a vote, computed the same way as the check of the vote.
+		InsufficientFreeBalance,
+		/// None of the votes of the account can be claimed yet.
+		NothingToUnlock,
+		/// The preimage of the proposal call hasn't been noted or can't be decoded.
+		CallUnavailable,
+		/// The track of the proposal doesn't allow its call.
+		CallNotAllowed,
+		/// The account has votes with the native token on open proposals, which would count
+		/// twice once delegated.
+		HasOpenVotes,
+		/// A ballot casts no votes on one of its proposals.
+		ZeroVotes,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Closes the proposals whose voting period ends in this block, up to
+		/// `MaxAutoClosePerBlock`. The rest are queued for `on_idle`.
+		fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+			let mut ending = ProposalsEndingAt::<T>::take(n);
+			let mut closed = 0;
+			while closed < T::MaxAutoClosePerBlock::get() && !ending.is_empty() {
+				Self::close_if_ongoing(ending.remove(0));
+				closed += 1;
+			}
+
+			let mut carried = Vec::new();
+			if !ending.is_empty() {
+				OverdueProposals::<T>::mutate(|queue| {
+					for proposal_id in ending {
+						if let Err(proposal_id) = queue.try_push(proposal_id) {
+							carried.push(proposal_id);
+						}
+					}
+				});
+			}
+
+			// If the queue is full, the remaining proposals stay in the expiry queue and are
+			// closed in the next block, ahead of the proposals ending in it. The proposals pushed
+			// out of a full block move on to the block after it.
+			let max_ending = T::MaxProposalsEndingPerBlock::get() as usize;
+			let mut block = n;
+			let mut moved: u64 = 0;
+			while !carried.is_empty() {
+				block = block.saturating_add(One::one());
+				carried.extend(ProposalsEndingAt::<T>::take(block));
+				let rest = carried.split_off(carried.len().min(max_ending));
+				ProposalsEndingAt::<T>::insert(block, BoundedVec::truncate_from(carried));
+				carried = rest;
+				moved += 1;
+			}
+
+			T::WeightInfo::on_initialize(closed)
+				.saturating_add(T::DbWeight::get().reads_writes(moved, moved))
+		}
+
+		/// Closes queued proposals as long as there is enough weight left in the block.
+		fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			if remaining_weight.any_lt(T::WeightInfo::on_idle(0)) {
+				return Weight::zero();
+			}
+
+			let mut queue = OverdueProposals::<T>::get();
+			let mut closed = 0;
+			while !queue.is_empty() && T::WeightInfo::on_idle(closed + 1).all_lte(remaining_weight)
+			{
+				Self::close_if_ongoing(queue.remove(0));
+				closed += 1;
+			}
+
+			if closed > 0 {
+				OverdueProposals::<T>::put(queue);
+			}
+
+			T::WeightInfo::on_idle(closed)
+		}
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// A dispatchable that registers voters.
 		///
-		/// The dispatch origin of this call must be Sudo.
+		/// The dispatch origin of this call must be `RegistrarOrigin` or Signed by a registrar
+		/// with quota left. Registering a voter uses one unit of the registrar's quota.
 		///
 		/// - `voter`: the AccountId to be registered in the voter pool.
 		///
 		/// Emits `VoterRegistered { voter }`.
 		#[pallet::call_index(0)]
-		#[pallet::weight(Weight::default())]
+		#[pallet::weight(T::WeightInfo::register_voters())]
 		pub fn register_voters(origin: OriginFor<T>, voter: T::AccountId) -> DispatchResult {
-			ensure_root(origin)?;
+			let registrar = Self::ensure_registrar(origin)?;
+
+			RegisteredAccounts::<T>::get(&voter)
+				.map_or(Ok(()), |_| Err(Error::<T>::VoterAlreadyRegistered))?;
+
+			if let Some(registrar) = registrar {
+				Registrars::<T>::try_mutate(&registrar, |quota| -> DispatchResult {
+					let quota = quota.as_mut().ok_or(DispatchError::BadOrigin)?;
+					*quota = quota.checked_sub(1).ok_or(Error::<T>::RegistrarQuotaExhausted)?;
+					Ok(())
+				})?;
+			}
+
+			RegisteredAccounts::<T>::insert(&voter, RegistrationKind::Manual);
+
+			Self::deposit_event(Event::VoterRegistered { voter });
+
+			Ok(())
+		}
+
+		/// A dispatchable that registers many voters at once.
+		///
+		/// The dispatch origin of this call must be `RegistrarOrigin` or Signed by a registrar.
+		/// Accounts that are already registered, or that are over the registrar's quota, are
+		/// skipped and reported instead of failing the whole batch.
+		///
+		/// - `voters`: the AccountIds to be registered, at most `MaxRegistrationBatch`.
+		///
+		/// Emits `VotersBatchRegistered { registered, skipped }`.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::register_voters_batch(voters.len() as u32))]
+		pub fn register_voters_batch(
+			origin: OriginFor<T>,
+			voters: Vec<T::AccountId>,
+		) -> DispatchResult {
+			let registrar = Self::ensure_registrar(origin)?;
+
+			ensure!(
+				voters.len() as u32 <= T::MaxRegistrationBatch::get(),
+				Error::<T>::TooManyVotersInBatch
+			);
+
+			let mut quota = registrar
+				.as_ref()
+				.map(|registrar| Registrars::<T>::get(registrar).ok_or(DispatchError::BadOrigin))
+				.transpose()?;
+
+			let mut registered = 0;
+			let mut skipped = Vec::new();
+			for voter in voters {
+				if RegisteredAccounts::<T>::contains_key(&voter) || quota == Some(0) {
+					skipped.push(voter);
+					continue;
+				}
+				if let Some(quota) = quota.as_mut() {
+					*quota -= 1;
+				}
+				RegisteredAccounts::<T>::insert(&voter, RegistrationKind::Manual);
+				registered += 1;
+			}
+
+			if let (Some(registrar), Some(quota)) = (registrar, quota) {
+				Registrars::<T>::insert(&registrar, quota);
+			}
+
+			Self::deposit_event(Event::VotersBatchRegistered { registered, skipped });
+
+			Ok(())
+		}
+
+		/// A dispatchable that lets an account register itself as a voter through its identity.
+		///
+		/// The dispatch origin of this call must be Signed and the identity of the sender must
+		/// meet the requirements of the configured `IdentityProvider`.
+		///
+		/// Emits `VoterRegistered { voter }`.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::register_self())]
+		pub fn register_self(origin: OriginFor<T>) -> DispatchResult {
+			let voter = ensure_signed(origin)?;
 
 			RegisteredAccounts::<T>::get(&voter)
 				.map_or(Ok(()), |_| Err(Error::<T>::VoterAlreadyRegistered))?;
 
-			RegisteredAccounts::<T>::insert(&voter, true);
+			ensure!(T::IdentityProvider::has_identity(&voter), Error::<T>::InsufficientIdentity);
+
+			RegisteredAccounts::<T>::insert(&voter, RegistrationKind::Identity);
 
 			Self::deposit_event(Event::VoterRegistered { voter });
 
@@ -263,37 +1064,38 @@ pub mod pallet {
 		/// The dispatch origin of this call must be Signed and the sender must
 		/// be a registered voter.
 		///
-		/// - `proposal_hash`: The hash of the proposal preimage.
+		/// The deposit of the track is held from the sender until the proposal closes.
+		///
+		/// - `track`: The track of the proposal, which decides when its voting period starts and
+		///   ends, the participation it needs and its deposit.
+		/// - `metadata`: The title and description of the proposal. Descriptions longer than
+		///   `MaxDescriptionLength` have to be noted with the preimage pallet first.
+		/// - `call`: An optional call, inline or as a noted preimage, to dispatch if the proposal
+		///   passes. The track has to allow the call.
 		///
-		/// Emits `ProposalCreated { proposal_id }`
+		/// Emits `ProposalCreated { proposal_id, description_hash }`
 		#[pallet::call_index(1)]
-		#[pallet::weight(Weight::default())]
+		#[pallet::weight(T::WeightInfo::make_proposal(
+			(metadata.title.len() as u32).saturating_add(metadata.description.length())
+		))]
 		pub fn make_proposal(
 			origin: OriginFor<T>,
-			proposal_description: Vec<u8>,
+			track: TrackId,
+			metadata: ProposalMetadata<T>,
+			call: Option<BoundedCallOf<T>>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
-			RegisteredAccounts::<T>::get(&who).ok_or(Error::<T>::NotRegistered)?;
-
-			let proposal = Proposal::<T> {
-				description: <T as frame_system::Config>::Hashing::hash(&proposal_description),
-				start_block: Self::get_current_block_number(),
-				ayes: BalanceOf::<T>::zero(),
-				nays: BalanceOf::<T>::zero(),
-				end: false,
-			};
-
-			// ValueQuery makes sure it returns 0 if no proposals exist.
-			let proposal_id = <ProposalIndex<T>>::get();
-			<ProposalPool<T>>::insert(proposal_id, proposal);
+			Self::ensure_eligible(&who)?;
 
-			Self::deposit_event(Event::ProposalCreated { proposal_id });
+			let deposit = T::Tracks::info(track).ok_or(Error::<T>::UnknownTrack)?.deposit;
+			if !deposit.is_zero() {
+				T::NativeBalance::hold(&HoldReason::ProposalDeposit.into(), &who, deposit)
+					.map_err(|_| Error::<T>::InsufficientDepositFunds)?;
+			}
 
-			// Prepare the next proposal id.
-			let new_proposal_id =
-				proposal_id.checked_add(&T::ProposalId::one()).ok_or(Error::<T>::Overflow)?;
-			<ProposalIndex<T>>::set(new_proposal_id);
+			let proposal_id = Self::do_make_proposal(track, metadata, call)?;
+			ProposalDeposits::<T>::insert(proposal_id, (who, deposit));
 
 			Ok(())
 		}
@@ -303,82 +1105,45 @@ pub mod pallet {
 		/// The dispatch origin of this call must be Signed and the sender must
 		/// be a registered voter.
 		///
-		/// - `votes`: The number of votes.
-		/// - `aye': true for 'Aye', False for 'Nay'.
+		/// - `votes`: The number of whole votes. Their cost is the square of the votes, however
+		///   they are split. `vote_with_amount` votes with an exact amount of tokens instead.
+		/// - `kind`: `Aye`, `Nay`, `Abstain`, or `Split` with the ayes and nays the votes are split
+		///   into.
 		/// - `proposal_id`: The id of the proposal to vote on.
+		/// - `conviction`: Multiplies the ayes or nays in exchange for lock periods during which
+		///   the tokens stay frozen after the voting period ends, if the vote wins. Without a
+		///   conviction the votes are counted as `Conviction::None`, at 0.1x and never locked.
 		///
 		/// Emits `VoteAddedTo { proposal_id, votes }` in case the vote has been added.
 		/// Emits `VoteRemovedOrCanceled { proposal_id }` in case the vote has been canceled or
 		/// removed.
 		#[pallet::call_index(2)]
-		#[pallet::weight(Weight::default())]
+		#[pallet::weight(T::WeightInfo::vote(T::MaxVotes::get()))]
 		pub fn vote(
 			origin: OriginFor<T>,
 			votes: BalanceOf<T>,
-			aye: bool,
+			kind: VoteKind<BalanceOf<T>>,
 			proposal_id: T::ProposalId,
+			conviction: Option<Conviction>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
-			RegisteredAccounts::<T>::get(&who).ok_or(Error::<T>::NotRegistered)?;
-
-			// Check if the proposal exists.
-			let mut proposal =
-				<ProposalPool<T>>::get(proposal_id).ok_or(Error::<T>::ProposalDoesNotExist)?;
-
-			// Check the proposal hasn't ended.
-			ensure!(!proposal.end, Error::<T>::VoteAlreadyEnded);
-
-			let required_tokens = votes.checked_mul(&votes).ok_or(Error::<T>::Overflow)?;
-			let account_balance =
-				<T::NativeBalance as fungible::Inspect<T::AccountId>>::total_balance(&who);
 
-			// Make sure the voter has enough tokens to vote.
-			ensure!(account_balance >= required_tokens, Error::<T>::InsufficientFunds);
-
-			// Prepare to update the voter's voting history.
-			let mut new_voting_history = BoundedVec::new();
-			let user_vote = UserVoteInfo { aye, proposal_id, votes };
-
-			// Check if the voter has voted before on this proposal and removes his votes.
-			if let Some((index, mut voting_history)) =
-				Self::find_existing_vote(who.clone(), proposal_id)
-			{
-				// Remove the votes from the proposal.
-				Self::remove_votes_from_proposal(
-					&mut proposal,
-					voting_history[index].aye,
-					voting_history[index].votes,
-				)?;
-
-				// Remove the votes from the voting history.
-				voting_history.remove(index);
-
-				VotingHistory::<T>::insert(who.clone(), voting_history.clone());
-
-				// Unfreeze the tokens if necessary.
-				Self::unfreeze(who.clone(), &mut voting_history)?;
-
-				new_voting_history = voting_history;
-			}
-
-			// Then act like he is a new voter and add his new vote.
-			// If the amount of votes is 0, do nothing.
-			if votes == BalanceOf::<T>::default() {
-				Self::deposit_event(Event::VoteRemovedOrCancelled { proposal_id });
-				return Ok(())
-			}
-
-			Self::freeze(who, user_vote, &mut new_voting_history, required_tokens)?;
-
-			Self::add_votes_to_proposal(&mut proposal, aye, votes)?;
-
-			<ProposalPool<T>>::insert(proposal_id, proposal);
+			// The ayes and nays are counted in `VotePrecision` units, like the votes.
+			let kind = match kind {
+				VoteKind::Split { aye, nay } => {
+					ensure!(aye.checked_add(&nay) == Some(votes), Error::<T>::InvalidSplit);
+					VoteKind::Split { aye: Self::vote_units(aye)?, nay: Self::vote_units(nay)? }
+				},
+				kind => kind,
+			};
+			let amount = votes.checked_mul(&votes).ok_or(Error::<T>::Overflow)?;
 
-			Self::deposit_event(Event::VoteAddedTo { proposal_id, votes });
-			Ok(())
+			Self::do_vote(who, amount, Self::vote_units(votes)?, kind, proposal_id, conviction)
 		}
 
 		/// A dispatchable that ends the vote if the voting period is finished.
+		/// Proposals are closed automatically, this is the fallback for proposals that couldn't be
+		/// closed in time by the hooks.
 		///
 		/// The dispatch origin of this call must be Signed and the sender can
 		/// be anyone.
@@ -389,229 +1154,1601 @@ pub mod pallet {
 		/// Emits `Event::ProposalResultAye { proposal_id }` in case the proposal is accepted.
 		/// Emits `Event::ProposalResultNay { proposal_id }` in case the proposal is rejected.
 		/// Emits `Event::ProposalResultTie { proposal_id }` in case the vote ends in a tie.
+		/// Emits `Event::ProposalResultNoQuorum { proposal_id }` in case too few voters voted.
+		/// Emits `Event::ProposalEnacted { proposal_id, result }` in case the proposal is accepted
+		/// and has a call attached.
 		#[pallet::call_index(3)]
-		#[pallet::weight(Weight::default())]
+		#[pallet::weight(T::WeightInfo::end_vote())]
 		pub fn end_vote(origin: OriginFor<T>, proposal_id: T::ProposalId) -> DispatchResult {
 			ensure_signed(origin)?;
 
-			let mut proposal =
+			let proposal =
 				<ProposalPool<T>>::get(proposal_id).ok_or(Error::<T>::ProposalDoesNotExist)?;
 
 			// Check the proposal hasn't ended.
-			ensure!(!proposal.end, Error::<T>::VoteAlreadyEnded);
+			ensure!(proposal.status.is_open(), Error::<T>::VoteAlreadyEnded);
+
+			// Proposals of a ballot round are voted on and closed with the round.
+			ensure!(!ProposalRound::<T>::contains_key(proposal_id), Error::<T>::ProposalInRound);
 
 			// Convert both block numbers to balances so we can compare them
-			let start_block = Self::convert_block_number_to_balance(proposal.start_block);
+			let end_block = Self::convert_block_number_to_balance(proposal.end_block);
 			let current_block =
 				Self::convert_block_number_to_balance(Self::get_current_block_number());
 
 			// Check if the proposal time has ended.
-			Self::proposal_ended(start_block, current_block, &mut proposal)?;
-
-			// Calculate the outcome of the vote.
-			match proposal.ayes.cmp(&proposal.nays) {
-				Ordering::Greater => Self::deposit_event(Event::ProposalResultAye { proposal_id }),
-				Ordering::Less => Self::deposit_event(Event::ProposalResultNay { proposal_id }),
-				Ordering::Equal => Self::deposit_event(Event::ProposalResultTie { proposal_id }),
-			}
+			Self::proposal_ended(end_block, current_block)?;
 
-			// Close the proposal.
-			<ProposalPool<T>>::insert(proposal_id, proposal);
+			Self::close_proposal(proposal_id, proposal);
 			Ok(())
 		}
 
 		/// A dispatchable that allows voters to reclaim their frozen tokens after a proposal has
-		/// been closed.
+		/// been closed. Ended proposals can be claimed in any order, the frozen amount is always
+		/// the cost of the most expensive vote left in the voting history.
 		///
-		/// The dispatch origin of this call must be Signed and the sender must
-		/// be a registered voter.
+		/// The dispatch origin of this call must be Signed and the sender must have voted on
+		/// the proposal. Deregistered, suspended and expired voters can still claim. Winning votes
+		/// cast with a conviction can only be claimed from their unlock block.
 		///
 		/// - `proposal_id`: The id of the proposal to close.
 		///
-		/// Emits `Event::TokensUnlocked` in case there are eligible tokens.
-		/// Emits `Event::NoTokensUnlocked` in case there aren't any eligible tokens.
+		/// Emits `Event::TokensUnlocked { who, amount_released, still_frozen }` in case there are
+		/// eligible tokens.
+		/// Emits `Event::NoTokensUnlocked { who, proposal_id, frozen }` in case the other votes
+		/// keep the frozen amount unchanged.
 		#[pallet::call_index(4)]
-		#[pallet::weight(Weight::default())]
+		#[pallet::weight(T::WeightInfo::claim_frozen_tokens(T::MaxVotes::get()))]
 		pub fn claim_frozen_tokens(
 			origin: OriginFor<T>,
 			proposal_id: T::ProposalId,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
-			RegisteredAccounts::<T>::get(&who).ok_or(Error::<T>::NotRegistered)?;
 
 			// Check if the proposal exists.
 			let proposal =
 				<ProposalPool<T>>::get(proposal_id).ok_or(Error::<T>::ProposalDoesNotExist)?;
 
 			// Check the proposal has ended.
-			ensure!(proposal.end, Error::<T>::VotingPeriodNotOver);
+			ensure!(!proposal.status.is_open(), Error::<T>::VotingPeriodNotOver);
 
 			// Check if there are votes for this proposal from this account.
-			let mut voting_history =
-				VotingHistory::<T>::get(who.clone()).ok_or(Error::<T>::NoVotes)?;
+			let (index, mut voting_history) =
+				Self::find_existing_vote(who.clone(), proposal_id).ok_or(Error::<T>::NoVotes)?;
 
-			// Get the highest amount of votes from this account's voting history.
-			let (index, max_freeze_proposal) = voting_history
-				.iter()
-				.enumerate()
-				.max_by_key(|(_, item)| item.proposal_id)
-				.ok_or(Error::<T>::NoVotes)?; // This should never return an error.
+			// Winning votes stay frozen until the lock periods of their conviction are over.
+			let user_vote = &voting_history[index];
+			ensure!(
+				!Self::vote_won(&proposal, user_vote.kind) ||
+					Self::get_current_block_number() >= user_vote.unlock_at,
+				Error::<T>::VoteLocked
+			);
 
-			// If the amount locked by this proposal is not the highest, don't do anything.
-			if !max_freeze_proposal.proposal_id.eq(&proposal_id) {
-				Self::deposit_event(Event::NoTokensUnlocked);
-				return Ok(());
+			// Remove the votes from the account voting history.
+			let claimed = voting_history.remove(index);
+			if voting_history.is_empty() {
+				VotingHistory::<T>::remove(&who);
+			} else {
+				VotingHistory::<T>::insert(&who, &voting_history);
 			}
 
-			// Remove the votes from the account voting history.
-			voting_history.remove(index);
-			VotingHistory::<T>::insert(who.clone(), voting_history.clone());
+			let previously_frozen = Self::frozen(&who, &claimed.asset);
+			let frozen = Self::unfreeze(&who, &voting_history, &claimed.asset)?;
+
+			if frozen < previously_frozen {
+				Self::deposit_event(Event::TokensUnlocked {
+					who,
+					amount_released: previously_frozen.saturating_sub(frozen),
+					still_frozen: frozen,
+				});
+			} else {
+				Self::deposit_event(Event::NoTokensUnlocked { who, proposal_id, frozen });
+			}
+
+			Ok(())
+		}
 
-			Self::unfreeze(who, &mut voting_history)?;
+		/// A dispatchable that removes an account from the pool of voters.
+		///
+		/// The votes of the account on proposals that are still open are removed from the
+		/// tallies and the tokens frozen for them are released. Votes on ended proposals are
+		/// kept so the account can still claim them. The delegation of the account is removed.
+		///
+		/// The dispatch origin of this call must be `RegistrarOrigin`.
+		///
+		/// - `voter`: the AccountId to remove from the voter pool.
+		///
+		/// Emits `VoterDeregistered { voter, votes_removed }`.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::deregister_voter(T::MaxVotes::get()))]
+		pub fn deregister_voter(origin: OriginFor<T>, voter: T::AccountId) -> DispatchResult {
+			T::RegistrarOrigin::ensure_origin(origin)?;
+
+			RegisteredAccounts::<T>::take(&voter).ok_or(Error::<T>::NotRegistered)?;
+			SuspendedUntil::<T>::remove(&voter);
+			RegistrationExpiry::<T>::remove(&voter);
+
+			let votes_removed = Self::remove_open_votes(&voter)?;
+			if let Some(delegation) = Delegations::<T>::get(&voter) {
+				Self::remove_delegation(&voter, delegation)?;
+			}
 
-			Self::deposit_event(Event::TokensUnlocked);
+			Self::deposit_event(Event::VoterDeregistered { voter, votes_removed });
 
 			Ok(())
 		}
-	}
-}
 
-impl<T: Config> Pallet<T> {
-	/// How to get the current block from the FRAME System Pallet.
-	pub fn get_current_block_number() -> BlockNumberFor<T> {
-		frame_system::Pallet::<T>::block_number()
-	}
+		/// A dispatchable that stops a voter from making proposals and voting until a block.
+		/// The votes of the voter are kept. Suspending until a block that has already been
+		/// reached lifts the suspension.
+		///
+		/// The dispatch origin of this call must be `RegistrarOrigin`.
+		///
+		/// - `voter`: the AccountId to suspend.
+		/// - `until`: the first block the voter can vote again.
+		///
+		/// Emits `VoterSuspended { voter, until }` or `VoterReinstated { voter }`.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::suspend_voter())]
+		pub fn suspend_voter(
+			origin: OriginFor<T>,
+			voter: T::AccountId,
+			until: BlockNumberFor<T>,
+		) -> DispatchResult {
+			T::RegistrarOrigin::ensure_origin(origin)?;
 
-	/// How to convert a block number to the balance type.
-	pub fn convert_block_number_to_balance(block_number: BlockNumberFor<T>) -> BalanceOf<T> {
-		T::BlockNumberToBalance::convert(block_number)
-	}
+			RegisteredAccounts::<T>::get(&voter).ok_or(Error::<T>::NotRegistered)?;
 
-	/// Remove a number of aye or nay votes from the proposal.
-	fn remove_votes_from_proposal(
-		proposal: &mut Proposal<T>,
-		aye: bool,
-		votes: BalanceOf<T>,
-	) -> Result<(), DispatchError> {
-		match aye {
-			true => {
-				proposal.ayes = proposal.ayes.checked_sub(&votes).ok_or(Error::<T>::Underflow)?;
-			},
-			false => {
-				proposal.nays = proposal.nays.checked_sub(&votes).ok_or(Error::<T>::Underflow)?;
-			},
+			if until > Self::get_current_block_number() {
+				SuspendedUntil::<T>::insert(&voter, until);
+				Self::deposit_event(Event::VoterSuspended { voter, until });
+			} else {
+				SuspendedUntil::<T>::remove(&voter);
+				Self::deposit_event(Event::VoterReinstated { voter });
+			}
+
+			Ok(())
 		}
 
-		Ok(())
+		/// A dispatchable that sets the block from which the registration of a voter is no
+		/// longer valid. The votes of the voter are kept.
+		///
+		/// The dispatch origin of this call must be `RegistrarOrigin`.
+		///
+		/// - `voter`: the AccountId of the voter.
+		/// - `expiry`: the block the registration expires at, or `None` to never expire.
+		///
+		/// Emits `RegistrationExpirySet { voter, expiry }`.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::set_registration_expiry())]
+		pub fn set_registration_expiry(
+			origin: OriginFor<T>,
+			voter: T::AccountId,
+			expiry: Option<BlockNumberFor<T>>,
+		) -> DispatchResult {
+			T::RegistrarOrigin::ensure_origin(origin)?;
+
+			RegisteredAccounts::<T>::get(&voter).ok_or(Error::<T>::NotRegistered)?;
+
+			RegistrationExpiry::<T>::set(&voter, expiry);
+
+			Self::deposit_event(Event::RegistrationExpirySet { voter, expiry });
+
+			Ok(())
+		}
+
+		/// A dispatchable that adds a registrar or updates its quota, or removes it.
+		///
+		/// The dispatch origin of this call must be Root.
+		///
+		/// - `registrar`: the AccountId of the registrar.
+		/// - `quota`: the number of voters the registrar can still register, or `None` to remove
+		///   the registrar.
+		///
+		/// Emits `RegistrarSet { registrar, quota }`.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::set_registrar())]
+		pub fn set_registrar(
+			origin: OriginFor<T>,
+			registrar: T::AccountId,
+			quota: Option<u32>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			Registrars::<T>::set(&registrar, quota);
+
+			Self::deposit_event(Event::RegistrarSet { registrar, quota });
+
+			Ok(())
+		}
+
+		/// A dispatchable that groups open proposals into a ballot round.
+		///
+		/// The proposals can't have votes yet. From now on they can only be voted on with
+		/// `cast_ballot` and are closed with the round.
+		///
+		/// The dispatch origin of this call must be `RoundOrigin`.
+		///
+		/// - `proposals`: the proposals of the round, at most `MaxProposalsPerRound`.
+		/// - `voice_credits`: the voice credits every voter can spend in the round.
+		/// - `duration`: the number of blocks the round is open for.
+		///
+		/// Emits `RoundCreated { round, proposals }`.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::create_round(proposals.len() as u32))]
+		pub fn create_round(
+			origin: OriginFor<T>,
+			proposals: Vec<T::ProposalId>,
+			voice_credits: BalanceOf<T>,
+			duration: BlockNumberFor<T>,
+		) -> DispatchResult {
+			T::RoundOrigin::ensure_origin(origin)?;
+
+			let bounded_proposals: BoundedVec<_, T::MaxProposalsPerRound> =
+				proposals.clone().try_into().map_err(|_| Error::<T>::TooManyProposalsInRound)?;
+			Self::ensure_unique(&proposals)?;
+
+			let round = NextRoundId::<T>::get();
+			for proposal_id in &proposals {
+				let proposal =
+					<ProposalPool<T>>::get(proposal_id).ok_or(Error::<T>::ProposalDoesNotExist)?;
+				ensure!(
+					proposal.status.is_open() &&
+						proposal.voters == 0 &&
+						!ProposalRound::<T>::contains_key(proposal_id),
+					Error::<T>::ProposalNotEligibleForRound
+				);
+				ProposalRound::<T>::insert(proposal_id, round);
+			}
+
+			let end = Self::get_current_block_number()
+				.checked_add(&duration)
+				.ok_or(Error::<T>::Overflow)?;
+			Rounds::<T>::insert(
+				round,
+				BallotRound { proposals: bounded_proposals, voice_credits, end, closed: false },
+			);
+			NextRoundId::<T>::put(round.checked_add(1).ok_or(Error::<T>::Overflow)?);
+
+			Self::deposit_event(Event::RoundCreated { round, proposals });
+
+			Ok(())
+		}
+
+		/// A dispatchable that casts a ballot in a round.
+		///
+		/// The ballot can spread votes over any of the proposals of the round. Casting `n` votes
+		/// costs `n * n` voice credits and the total cost can't exceed the voice credits of the
+		/// round. Every voter can cast one ballot per round, with at least one vote on each of its
+		/// proposals, once their voting periods have started. Ballots lock no tokens, so their
+		/// votes are counted like votes cast with `Conviction::None`.
+		///
+		/// The dispatch origin of this call must be Signed and the sender must
+		/// be a registered voter.
+		///
+		/// - `round`: the id of the ballot round.
+		/// - `votes`: the proposals voted on, with `true` for 'Aye' and the number of votes.
+		///
+		/// Emits `BallotCast { round, who, cost }`.
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::cast_ballot(votes.len() as u32))]
+		pub fn cast_ballot(
+			origin: OriginFor<T>,
+			round: RoundId,
+			votes: Vec<(T::ProposalId, bool, BalanceOf<T>)>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_eligible(&who)?;
+
+			let ballot_round = Rounds::<T>::get(round).ok_or(Error::<T>::RoundDoesNotExist)?;
+			ensure!(!ballot_round.closed, Error::<T>::RoundAlreadyClosed);
+			ensure!(Self::get_current_block_number() < ballot_round.end, Error::<T>::RoundOver);
+			ensure!(!Ballots::<T>::contains_key(round, &who), Error::<T>::BallotAlreadyCast);
+			ensure!(
+				votes.len() as u32 <= T::MaxProposalsPerRound::get(),
+				Error::<T>::TooManyProposalsInRound
+			);
+			Self::ensure_unique(
+				&votes.iter().map(|(proposal_id, _, _)| *proposal_id).collect::<Vec<_>>(),
+			)?;
+
+			let mut cost = BalanceOf::<T>::zero();
+			for (proposal_id, _, amount) in &votes {
+				ensure!(
+					ballot_round.proposals.contains(proposal_id),
+					Error::<T>::ProposalNotInRound
+				);
+				// Proposals killed or cancelled during the round are closed.
+				let proposal =
+					<ProposalPool<T>>::get(proposal_id).ok_or(Error::<T>::ProposalDoesNotExist)?;
+				ensure!(proposal.status.is_open(), Error::<T>::VoteAlreadyEnded);
+				ensure!(
+					Self::get_current_block_number() >= proposal.start_block,
+					Error::<T>::VotingNotStarted
+				);
+				ensure!(!amount.is_zero(), Error::<T>::ZeroVotes);
+				let vote_cost = amount.checked_mul(amount).ok_or(Error::<T>::Overflow)?;
+				cost = cost.checked_add(&vote_cost).ok_or(Error::<T>::Overflow)?;
+			}
+			ensure!(cost <= ballot_round.voice_credits, Error::<T>::InsufficientVoiceCredits);
+
+			for (proposal_id, aye, amount) in votes {
+				let mut proposal =
+					<ProposalPool<T>>::get(proposal_id).ok_or(Error::<T>::ProposalDoesNotExist)?;
+				let kind = if aye { VoteKind::Aye } else { VoteKind::Nay };
+				let votes = Conviction::None.votes(Self::vote_units(amount)?);
+				Self::add_votes_to_proposal(&mut proposal, kind, votes)?;
+				<ProposalPool<T>>::insert(proposal_id, proposal);
+			}
+			Ballots::<T>::insert(round, &who, cost);
+
+			Self::deposit_event(Event::BallotCast { round, who, cost });
+
+			Ok(())
+		}
+
+		/// A dispatchable that tallies and closes every proposal of a ballot round at once.
+		///
+		/// The dispatch origin of this call must be Signed and the sender can
+		/// be anyone.
+		///
+		/// - `round`: the id of the ballot round.
+		///
+		/// Emits the result events of every proposal of the round, then
+		/// `RoundClosed { round }`.
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::close_round(T::MaxProposalsPerRound::get()))]
+		pub fn close_round(origin: OriginFor<T>, round: RoundId) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let mut ballot_round = Rounds::<T>::get(round).ok_or(Error::<T>::RoundDoesNotExist)?;
+			ensure!(!ballot_round.closed, Error::<T>::RoundAlreadyClosed);
+			ensure!(Self::get_current_block_number() >= ballot_round.end, Error::<T>::RoundNotOver);
+
+			for proposal_id in &ballot_round.proposals {
+				let proposal =
+					<ProposalPool<T>>::get(proposal_id).ok_or(Error::<T>::ProposalDoesNotExist)?;
+				// Proposals killed during the round are already closed.
+				if proposal.status.is_open() {
+					Self::close_proposal(*proposal_id, proposal);
+				}
+			}
+
+			ballot_round.closed = true;
+			Rounds::<T>::insert(round, ballot_round);
+
+			Self::deposit_event(Event::RoundClosed { round });
+
+			Ok(())
+		}
+
+		/// A dispatchable that kills a spam proposal.
+		///
+		/// The proposal is closed without a result and its deposit is slashed. Voters can claim
+		/// their frozen tokens right away.
+		///
+		/// The dispatch origin of this call must be `CancelOrigin`.
+		///
+		/// - `proposal_id`: the id of the proposal to kill.
+		///
+		/// Emits `DepositSlashed { proposal_id, who, amount }` if the proposal has a deposit,
+		/// then `ProposalKilled { proposal_id }`.
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::kill_proposal())]
+		pub fn kill_proposal(origin: OriginFor<T>, proposal_id: T::ProposalId) -> DispatchResult {
+			T::CancelOrigin::ensure_origin(origin)?;
+
+			let proposal =
+				<ProposalPool<T>>::get(proposal_id).ok_or(Error::<T>::ProposalDoesNotExist)?;
+			ensure!(proposal.status.is_open(), Error::<T>::VoteAlreadyEnded);
+
+			Self::cancel(proposal_id, proposal);
+			Self::settle_deposit(proposal_id, false);
+
+			Self::deposit_event(Event::ProposalKilled { proposal_id });
+
+			Ok(())
+		}
+
+		/// A dispatchable that allows the proposer to withdraw a proposal, for example to fix a
+		/// typo.
+		///
+		/// The proposal can be withdrawn until it closes as long as it has no votes, and during
+		/// the first `WithdrawalPeriod` blocks of the voting period otherwise. Proposals of a ballot
+		/// round can't be withdrawn. The deposit is refunded and voters can claim their frozen
+		/// tokens right away.
+		///
+		/// The dispatch origin of this call must be Signed and the sender must be the proposer.
+		///
+		/// - `proposal_id`: the id of the proposal to withdraw.
+		///
+		/// Emits `DepositRefunded { proposal_id, who, amount }` if the proposal has a deposit,
+		/// then `ProposalWithdrawn { proposal_id }`.
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::withdraw_proposal())]
+		pub fn withdraw_proposal(
+			origin: OriginFor<T>,
+			proposal_id: T::ProposalId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let proposal =
+				<ProposalPool<T>>::get(proposal_id).ok_or(Error::<T>::ProposalDoesNotExist)?;
+			ensure!(proposal.status.is_open(), Error::<T>::VoteAlreadyEnded);
+			ensure!(!ProposalRound::<T>::contains_key(proposal_id), Error::<T>::ProposalInRound);
+
+			// Proposals made before the proposer was recorded can't be withdrawn.
+			let (proposer, _) =
+				ProposalDeposits::<T>::get(proposal_id).ok_or(Error::<T>::NotProposer)?;
+			ensure!(proposer == who, Error::<T>::NotProposer);
+
+			let no_votes = proposal.voters == 0;
+			let grace_end = proposal.start_block.saturating_add(T::WithdrawalPeriod::get());
+			ensure!(
+				no_votes || Self::get_current_block_number() <= grace_end,
+				Error::<T>::WithdrawalPeriodOver
+			);
+
+			Self::cancel(proposal_id, proposal);
+			Self::settle_deposit(proposal_id, true);
+
+			Self::deposit_event(Event::ProposalWithdrawn { proposal_id });
+
+			Ok(())
+		}
+
+		/// A dispatchable that cancels an open proposal.
+		///
+		/// The proposal is closed without a result and its deposit is refunded. Voters can claim
+		/// their frozen tokens right away.
+		///
+		/// The dispatch origin of this call must be `CancelOrigin`.
+		///
+		/// - `proposal_id`: the id of the proposal to cancel.
+		///
+		/// Emits `DepositRefunded { proposal_id, who, amount }` if the proposal has a deposit,
+		/// then `ProposalCancelled { proposal_id }`.
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::cancel_proposal())]
+		pub fn cancel_proposal(origin: OriginFor<T>, proposal_id: T::ProposalId) -> DispatchResult {
+			T::CancelOrigin::ensure_origin(origin)?;
+
+			let proposal =
+				<ProposalPool<T>>::get(proposal_id).ok_or(Error::<T>::ProposalDoesNotExist)?;
+			ensure!(proposal.status.is_open(), Error::<T>::VoteAlreadyEnded);
+
+			Self::cancel(proposal_id, proposal);
+			Self::settle_deposit(proposal_id, true);
+
+			Self::deposit_event(Event::ProposalCancelled { proposal_id });
+
+			Ok(())
+		}
+
+		/// A dispatchable that delegates the votes of an amount of tokens to another voter.
+		///
+		/// The tokens are frozen and their square root, multiplied by the conviction, is added to
+		/// every vote of the target on open proposals, current and future, on the side it voted.
+		/// Split votes of the target don't carry delegated votes. The caller can't vote while it
+		/// delegates, and its votes with the native token on open proposals have to be removed
+		/// first.
+		///
+		/// The dispatch origin of this call must be Signed and the sender must be a registered
+		/// voter.
+		///
+		/// - `to`: The registered voter to delegate to.
+		/// - `conviction`: Multiplies the delegated votes. The tokens stay frozen for its lock
+		///   periods once the delegation is removed.
+		/// - `balance`: The tokens to delegate the votes of.
+		///
+		/// Emits `Delegated { who, target, votes }`.
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::delegate(T::MaxVotes::get()))]
+		pub fn delegate(
+			origin: OriginFor<T>,
+			to: T::AccountId,
+			conviction: Conviction,
+			balance: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_eligible(&who)?;
+			ensure!(
+				who != to && RegisteredAccounts::<T>::contains_key(&to),
+				Error::<T>::InvalidDelegate
+			);
+			ensure!(!Delegations::<T>::contains_key(&who), Error::<T>::AlreadyDelegating);
+			// The same tokens would back the votes of the account and the delegated ones.
+			ensure!(!Self::has_open_native_votes(&who), Error::<T>::HasOpenVotes);
+
+			let shortfall = balance.saturating_sub(Self::freezable_balance(&who, &None));
+			Self::ensure_affordable(&who, &None, balance, shortfall)?;
+
+			let votes = conviction.votes(Self::amount_votes(balance)?);
+			let delegated =
+				DelegatedVotes::<T>::get(&to).checked_add(&votes).ok_or(Error::<T>::Overflow)?;
+			Self::set_delegated_votes(&to, delegated)?;
+
+			Delegations::<T>::insert(
+				&who,
+				Delegation { target: to.clone(), conviction, balance, votes },
+			);
+			Self::update_delegation_freeze(&who)?;
+
+			Self::deposit_event(Event::Delegated { who, target: to, votes });
+			Ok(())
+		}
+
+		/// A dispatchable that removes the delegation of the caller.
+		///
+		/// The delegated votes are removed from the votes of the target on open proposals. The
+		/// tokens frozen for the delegation stay frozen for the lock periods of its conviction,
+		/// from now on, and are released with `unlock_delegation`. Delegations without a lock
+		/// period release their tokens right away.
+		///
+		/// The dispatch origin of this call must be Signed and the sender must delegate its votes.
+		///
+		/// Emits `Undelegated { who, target }`.
+		#[pallet::call_index(18)]
+		#[pallet::weight(T::WeightInfo::undelegate(T::MaxVotes::get()))]
+		pub fn undelegate(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let delegation = Delegations::<T>::get(&who).ok_or(Error::<T>::NotDelegating)?;
+			let lock_periods = delegation.conviction.lock_periods();
+			if lock_periods > 0 {
+				let unlock_at = Self::get_current_block_number().saturating_add(
+					T::VoteLockingPeriod::get().saturating_mul(lock_periods.into()),
+				);
+				// A lock left by an earlier delegation is extended, never shortened.
+				let lock = match DelegationLocks::<T>::get(&who) {
+					Some((previous_unlock_at, previous_balance)) => (
+						unlock_at.max(previous_unlock_at),
+						delegation.balance.max(previous_balance),
+					),
+					None => (unlock_at, delegation.balance),
+				};
+				DelegationLocks::<T>::insert(&who, lock);
+			}
+
+			Self::remove_delegation(&who, delegation)
+		}
+
+		/// Votes on a proposal by locking exactly `amount` tokens, for the square root of the
+		/// amount in votes rounded down to `VotePrecision`. A zero amount removes the vote.
+		/// Split votes have to be cast with `vote`.
+		#[pallet::call_index(19)]
+		#[pallet::weight(T::WeightInfo::vote(T::MaxVotes::get()))]
+		pub fn vote_with_amount(
+			origin: OriginFor<T>,
+			amount: BalanceOf<T>,
+			kind: VoteKind<BalanceOf<T>>,
+			proposal_id: T::ProposalId,
+			conviction: Option<Conviction>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!matches!(kind, VoteKind::Split { .. }), Error::<T>::InvalidSplit);
+
+			Self::do_vote(who, amount, Self::amount_votes(amount)?, kind, proposal_id, conviction)
+		}
+
+		/// Claims every vote of an account on an ended proposal whose lock periods are over, and
+		/// recomputes its freezes once.
+		///
+		/// The dispatch origin of this call must be Signed, by any account.
+		///
+		/// - `who`: The account whose votes are claimed.
+		///
+		/// Emits `TokensUnlocked { who, amount_released, still_frozen }` for the native token and
+		/// every asset of the claimed votes.
+		#[pallet::call_index(20)]
+		#[pallet::weight(T::WeightInfo::unlock_all(T::MaxVotes::get()))]
+		pub fn unlock_all(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let voting_history = VotingHistory::<T>::get(&who).ok_or(Error::<T>::NoVotes)?;
+			let current_block = Self::get_current_block_number();
+			let (claimed, kept): (Vec<_>, Vec<_>) =
+				voting_history.into_iter().partition(|user_vote| {
+					<ProposalPool<T>>::get(user_vote.proposal_id).map_or(false, |proposal| {
+						!proposal.status.is_open() &&
+							(!Self::vote_won(&proposal, user_vote.kind) ||
+								current_block >= user_vote.unlock_at)
+					})
+				});
+			ensure!(!claimed.is_empty(), Error::<T>::NothingToUnlock);
+
+			let voting_history = BoundedVec::<_, T::MaxVotes>::truncate_from(kept);
+			if voting_history.is_empty() {
+				VotingHistory::<T>::remove(&who);
+			} else {
+				VotingHistory::<T>::insert(&who, &voting_history);
+			}
+
+			let mut assets = Vec::new();
+			for user_vote in claimed {
+				if !assets.contains(&user_vote.asset) {
+					assets.push(user_vote.asset);
+				}
+			}
+			for asset in assets {
+				let previously_frozen = Self::frozen(&who, &asset);
+				let still_frozen = Self::unfreeze(&who, &voting_history, &asset)?;
+				Self::deposit_event(Event::TokensUnlocked {
+					who: who.clone(),
+					amount_released: previously_frozen.saturating_sub(still_frozen),
+					still_frozen,
+				});
+			}
+
+			Ok(())
+		}
+
+		/// A dispatchable that releases the tokens of the removed delegations of the caller once
+		/// their lock periods are over.
+		///
+		/// The dispatch origin of this call must be Signed.
+		#[pallet::call_index(21)]
+		#[pallet::weight(T::WeightInfo::unlock_delegation())]
+		pub fn unlock_delegation(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let (unlock_at, _) =
+				DelegationLocks::<T>::get(&who).ok_or(Error::<T>::NothingToUnlock)?;
+			ensure!(Self::get_current_block_number() >= unlock_at, Error::<T>::DelegationLocked);
+
+			Self::update_delegation_freeze(&who)
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// How to get the current block from the FRAME System Pallet.
+	pub fn get_current_block_number() -> BlockNumberFor<T> {
+		frame_system::Pallet::<T>::block_number()
+	}
+
+	/// How to convert a block number to the balance type.
+	pub fn convert_block_number_to_balance(block_number: BlockNumberFor<T>) -> BalanceOf<T> {
+		T::BlockNumberToBalance::convert(block_number)
+	}
+
+	/// Checks that the account is registered, not suspended and its registration hasn't expired.
+	/// If it registered through its identity, also checks the identity still meets the
+	/// requirements.
+	pub fn ensure_eligible(who: &T::AccountId) -> DispatchResult {
+		let kind = RegisteredAccounts::<T>::get(who).ok_or(Error::<T>::NotRegistered)?;
+
+		let now = Self::get_current_block_number();
+		if let Some(until) = SuspendedUntil::<T>::get(who) {
+			ensure!(now >= until, Error::<T>::VoterSuspended);
+		}
+		if let Some(expiry) = RegistrationExpiry::<T>::get(who) {
+			ensure!(now < expiry, Error::<T>::RegistrationExpired);
+		}
+
+		if kind == RegistrationKind::Identity {
+			ensure!(T::IdentityProvider::has_identity(who), Error::<T>::InsufficientIdentity);
+		}
+
+		Ok(())
+	}
+
+	/// Removes the votes of the account on proposals that are still open from their tallies and
+	/// from the voting history, then updates the freeze. Returns the number of removed votes.
+	fn remove_open_votes(who: &T::AccountId) -> Result<u32, DispatchError> {
+		let Some(mut voting_history) = VotingHistory::<T>::get(who) else { return Ok(0) };
+
+		let mut votes_removed = 0;
+		// The tokens whose freeze has to be recomputed, always including the native token.
+		let mut assets = sp_std::vec![None];
+		let mut index = 0;
+		while index < voting_history.len() {
+			let user_vote = &voting_history[index];
+			match <ProposalPool<T>>::get(user_vote.proposal_id) {
+				Some(mut proposal) if proposal.status.is_open() => {
+					Self::remove_votes_from_proposal(
+						&mut proposal,
+						user_vote.kind,
+						user_vote.tallied_votes(),
+					)?;
+					Self::remove_delegated_votes(
+						&mut proposal,
+						user_vote.kind,
+						Self::delegated_votes(who, &user_vote.asset),
+					)?;
+					<ProposalPool<T>>::insert(user_vote.proposal_id, proposal);
+					let removed = voting_history.remove(index);
+					if !assets.contains(&removed.asset) {
+						assets.push(removed.asset);
+					}
+					votes_removed += 1;
+				},
+				_ => index += 1,
+			}
+		}
+
+		if voting_history.is_empty() {
+			VotingHistory::<T>::remove(who);
+		} else {
+			VotingHistory::<T>::insert(who, &voting_history);
+		}
+		for asset in assets {
+			Self::unfreeze(who, &voting_history, &asset)?;
+		}
+
+		Ok(votes_removed)
+	}
+
+	/// Removes a delegation, its votes from the target and the freeze of its tokens.
+	fn remove_delegation(who: &T::AccountId, delegation: Delegation<T>) -> DispatchResult {
+		let delegated = DelegatedVotes::<T>::get(&delegation.target)
+			.checked_sub(&delegation.votes)
+			.ok_or(Error::<T>::Underflow)?;
+		Self::set_delegated_votes(&delegation.target, delegated)?;
+		Delegations::<T>::remove(who);
+		Self::update_delegation_freeze(who)?;
+
+		Self::deposit_event(Event::Undelegated { who: who.clone(), target: delegation.target });
+		Ok(())
+	}
+
+	/// Freezes the tokens of the delegation of an account, or of its removed delegations that
+	/// are still locked, whichever is larger. Expired locks are removed.
+	fn update_delegation_freeze(who: &T::AccountId) -> DispatchResult {
+		let mut frozen = Delegations::<T>::get(who)
+			.map_or_else(Zero::zero, |delegation: Delegation<T>| delegation.balance);
+		if let Some((unlock_at, balance)) = DelegationLocks::<T>::get(who) {
+			if Self::get_current_block_number() >= unlock_at {
+				DelegationLocks::<T>::remove(who);
+			} else {
+				frozen = frozen.max(balance);
+			}
+		}
+
+		if frozen.is_zero() {
+			T::NativeBalance::thaw(&FreezeReason::Delegation.into(), who)
+		} else {
+			T::NativeBalance::set_freeze(&FreezeReason::Delegation.into(), who, frozen)
+		}
+	}
+
+	/// Whether an account has votes with the native token on proposals that are still open.
+	fn has_open_native_votes(who: &T::AccountId) -> bool {
+		VotingHistory::<T>::get(who).unwrap_or_default().iter().any(|user_vote| {
+			user_vote.asset.is_none() &&
+				<ProposalPool<T>>::get(user_vote.proposal_id)
+					.map_or(false, |proposal| proposal.status.is_open())
+		})
+	}
+
+	/// Sets the votes delegated to a voter and moves its votes on open proposals from the votes
+	/// delegated until now to the new ones.
+	fn set_delegated_votes(delegate: &T::AccountId, delegated: BalanceOf<T>) -> DispatchResult {
+		let previously_delegated = DelegatedVotes::<T>::get(delegate);
+		for user_vote in VotingHistory::<T>::get(delegate).unwrap_or_default() {
+			// Delegations freeze the native token, so they don't count for votes with an asset.
+			if user_vote.asset.is_some() {
+				continue
+			}
+			match <ProposalPool<T>>::get(user_vote.proposal_id) {
+				Some(mut proposal) if proposal.status.is_open() => {
+					Self::remove_delegated_votes(
+						&mut proposal,
+						user_vote.kind,
+						previously_delegated,
+					)?;
+					Self::add_delegated_votes(&mut proposal, user_vote.kind, delegated)?;
+					<ProposalPool<T>>::insert(user_vote.proposal_id, proposal);
+				},
+				_ => {},
+			}
+		}
+
+		if delegated.is_zero() {
+			DelegatedVotes::<T>::remove(delegate);
+		} else {
+			DelegatedVotes::<T>::insert(delegate, delegated);
+		}
+		Ok(())
+	}
+
+	/// Returns `None` for `RegistrarOrigin` and the account for a Signed origin, which has to be
+	/// checked against `Registrars` by the caller.
+	fn ensure_registrar(origin: OriginFor<T>) -> Result<Option<T::AccountId>, DispatchError> {
+		match T::RegistrarOrigin::try_origin(origin) {
+			Ok(_) => Ok(None),
+			Err(origin) => Ok(Some(frame_system::ensure_signed(origin)?)),
+		}
 	}
 
-	/// Add a number of aye or nay votes to the proposal.
+	/// Checks that no proposal appears more than once.
+	fn ensure_unique(proposals: &[T::ProposalId]) -> DispatchResult {
+		for (index, proposal_id) in proposals.iter().enumerate() {
+			ensure!(!proposals[..index].contains(proposal_id), Error::<T>::DuplicateProposal);
+		}
+		Ok(())
+	}
+
+	/// Casts `votes` votes, in `VotePrecision` units, on a proposal locking `amount` tokens, or
+	/// removes the vote of the account if `amount` is zero. Split votes must already be in
+	/// `VotePrecision` units.
+	fn do_vote(
+		who: T::AccountId,
+		amount: BalanceOf<T>,
+		votes: BalanceOf<T>,
+		kind: VoteKind<BalanceOf<T>>,
+		proposal_id: T::ProposalId,
+		conviction: Option<Conviction>,
+	) -> DispatchResult {
+		Self::ensure_eligible(&who)?;
+		let conviction = conviction.unwrap_or(Conviction::None);
+
+		// Votes cast before delegating can still be removed.
+		ensure!(
+			amount.is_zero() || !Delegations::<T>::contains_key(&who),
+			Error::<T>::AlreadyDelegating
+		);
+
+		match kind {
+			VoteKind::Split { .. } | VoteKind::Abstain =>
+				ensure!(conviction == Conviction::None, Error::<T>::InvalidConviction),
+			VoteKind::Aye | VoteKind::Nay => {},
+		}
+
+		// Check if the proposal exists.
+		let mut proposal =
+			<ProposalPool<T>>::get(proposal_id).ok_or(Error::<T>::ProposalDoesNotExist)?;
+
+		// Check the proposal hasn't ended.
+		ensure!(proposal.status.is_open(), Error::<T>::VoteAlreadyEnded);
+
+		// Proposals of a ballot round are voted on and closed with the round.
+		ensure!(!ProposalRound::<T>::contains_key(proposal_id), Error::<T>::ProposalInRound);
+
+		// Check the voting period has started.
+		ensure!(
+			Self::get_current_block_number() >= proposal.start_block,
+			Error::<T>::VotingNotStarted
+		);
+
+		// The track of the proposal decides which token the votes lock.
+		let asset = Self::track_asset(proposal.track);
+
+		// Prepare to update the voter's voting history.
+		let mut new_voting_history = BoundedVec::new();
+		let lock_periods = conviction.lock_periods();
+		let unlock_at = proposal
+			.end_block
+			.saturating_add(T::VoteLockingPeriod::get().saturating_mul(lock_periods.into()));
+		let user_vote = UserVoteInfo {
+			kind,
+			proposal_id,
+			votes,
+			amount,
+			conviction,
+			unlock_at,
+			asset: asset.clone(),
+		};
+		let tallied_votes = user_vote.tallied_votes();
+
+		// Check if the voter has voted before on this proposal and removes his votes.
+		if let Some((index, mut voting_history)) =
+			Self::find_existing_vote(who.clone(), proposal_id)
+		{
+			// Remove the votes from the proposal, along with the votes delegated to the voter.
+			Self::remove_votes_from_proposal(
+				&mut proposal,
+				voting_history[index].kind,
+				voting_history[index].tallied_votes(),
+			)?;
+			Self::remove_delegated_votes(
+				&mut proposal,
+				voting_history[index].kind,
+				Self::delegated_votes(&who, &voting_history[index].asset),
+			)?;
+
+			// Remove the votes from the voting history.
+			let old_vote = voting_history.remove(index);
+
+			VotingHistory::<T>::insert(who.clone(), voting_history.clone());
+
+			// Unfreeze the tokens if necessary.
+			Self::unfreeze(&who, &voting_history, &old_vote.asset)?;
+
+			new_voting_history = voting_history;
+		}
+
+		// Then act like he is a new voter and add his new vote.
+		// If the amount of tokens is 0, only the removal of the old vote is stored.
+		if amount.is_zero() {
+			<ProposalPool<T>>::insert(proposal_id, proposal);
+			Self::deposit_event(Event::VoteRemovedOrCancelled { proposal_id });
+			return Ok(())
+		}
+
+		// Make sure the voter has enough tokens to vote, once the old vote released its tokens.
+		let shortfall = Self::lock_shortfall(&who, proposal_id, &asset, amount);
+		Self::ensure_affordable(&who, &asset, amount, shortfall)?;
+
+		Self::add_delegated_votes(&mut proposal, kind, Self::delegated_votes(&who, &asset))?;
+		Self::freeze(who, user_vote, &mut new_voting_history)?;
+
+		Self::add_votes_to_proposal(&mut proposal, kind, tallied_votes)?;
+
+		<ProposalPool<T>>::insert(proposal_id, proposal);
+
+		Self::deposit_event(Event::VoteAddedTo { proposal_id, votes });
+		Ok(())
+	}
+
+	/// Converts whole votes to `VotePrecision` units.
+	fn vote_units(votes: BalanceOf<T>) -> Result<BalanceOf<T>, DispatchError> {
+		Ok(votes.checked_mul(&T::VotePrecision::get().into()).ok_or(Error::<T>::Overflow)?)
+	}
+
+	/// The votes, in `VotePrecision` units, bought by locking `amount` tokens: the square root of
+	/// the amount, rounded down to the precision.
+	fn amount_votes(amount: BalanceOf<T>) -> Result<BalanceOf<T>, DispatchError> {
+		let precision: BalanceOf<T> = T::VotePrecision::get().into();
+		let scaled = precision
+			.checked_mul(&precision)
+			.and_then(|square| amount.checked_mul(&square))
+			.ok_or(Error::<T>::Overflow)?;
+		Ok(scaled.integer_sqrt())
+	}
+
+	/// Creates a proposal on a track and returns its id. Its voting period starts after the start
+	/// delay of the track.
+	fn do_make_proposal(
+		track: TrackId,
+		metadata: ProposalMetadata<T>,
+		call: Option<BoundedCallOf<T>>,
+	) -> Result<T::ProposalId, DispatchError> {
+		let description_hash = match &metadata.description {
+			ProposalDescription::Inline(text) => <T as frame_system::Config>::Hashing::hash(text),
+			ProposalDescription::Preimage { hash, len } => {
+				let text = T::Preimages::fetch(hash, Some(*len))
+					.map_err(|_| Error::<T>::DescriptionUnavailable)?;
+				<T as frame_system::Config>::Hashing::hash(&text)
+			},
+		};
+		let track_info = T::Tracks::info(track).ok_or(Error::<T>::UnknownTrack)?;
+		if let Some(call) = &call {
+			let (call, _) = T::Preimages::peek(call).map_err(|_| Error::<T>::CallUnavailable)?;
+			ensure!(T::Tracks::authorize_call(track, &call), Error::<T>::CallNotAllowed);
+		}
+		let start_block = Self::get_current_block_number()
+			.checked_add(&track_info.start_delay)
+			.ok_or(Error::<T>::Overflow)?;
+		let end_block =
+			start_block.checked_add(&track_info.duration).ok_or(Error::<T>::Overflow)?;
+		let proposal = Proposal::<T> {
+			description: description_hash,
+			track,
+			start_block,
+			end_block,
+			ayes: BalanceOf::<T>::zero(),
+			nays: BalanceOf::<T>::zero(),
+			abstain: BalanceOf::<T>::zero(),
+			voters: 0,
+			status: ProposalStatus::Pending,
+			closed_at: None,
+			call,
+		};
+
+		// ValueQuery makes sure it returns 0 if no proposals exist.
+		let proposal_id = <ProposalIndex<T>>::get();
+
+		// Schedule the proposal to be closed when the voting period ends.
+		ProposalsEndingAt::<T>::try_mutate(end_block, |ending| ending.try_push(proposal_id))
+			.map_err(|_| Error::<T>::TooManyProposalsEnding)?;
+
+		ProposalMetadataOf::<T>::insert(proposal_id, metadata);
+		// Keep the preimages of the proposal available until it is closed.
+		for hash in Self::proposal_preimages(proposal_id, &proposal) {
+			T::Preimages::request(&hash);
+		}
+		<ProposalPool<T>>::insert(proposal_id, proposal);
+
+		Self::deposit_event(Event::ProposalCreated { proposal_id, description_hash });
+
+		// Prepare the next proposal id.
+		let new_proposal_id =
+			proposal_id.checked_add(&T::ProposalId::one()).ok_or(Error::<T>::Overflow)?;
+		<ProposalIndex<T>>::set(new_proposal_id);
+
+		Ok(proposal_id)
+	}
+
+	/// Remove the votes of a voter from the proposal.
+	fn remove_votes_from_proposal(
+		proposal: &mut Proposal<T>,
+		kind: VoteKind<BalanceOf<T>>,
+		votes: BalanceOf<T>,
+	) -> Result<(), DispatchError> {
+		let (ayes, nays, abstain) = Self::split_votes(kind, votes);
+		proposal.ayes = proposal.ayes.checked_sub(&ayes).ok_or(Error::<T>::Underflow)?;
+		proposal.nays = proposal.nays.checked_sub(&nays).ok_or(Error::<T>::Underflow)?;
+		proposal.abstain = proposal.abstain.checked_sub(&abstain).ok_or(Error::<T>::Underflow)?;
+		proposal.voters = proposal.voters.checked_sub(1).ok_or(Error::<T>::Underflow)?;
+
+		Ok(())
+	}
+
+	/// Add the votes of a voter to the proposal.
 	fn add_votes_to_proposal(
 		proposal: &mut Proposal<T>,
-		aye: bool,
+		kind: VoteKind<BalanceOf<T>>,
 		votes: BalanceOf<T>,
 	) -> Result<(), DispatchError> {
-		match aye {
-			true => {
-				proposal.ayes = proposal.ayes.checked_add(&votes).ok_or(Error::<T>::Overflow)?;
-			},
-			false => {
-				proposal.nays = proposal.nays.checked_add(&votes).ok_or(Error::<T>::Overflow)?;
-			},
+		let (ayes, nays, abstain) = Self::split_votes(kind, votes);
+		proposal.ayes = proposal.ayes.checked_add(&ayes).ok_or(Error::<T>::Overflow)?;
+		proposal.nays = proposal.nays.checked_add(&nays).ok_or(Error::<T>::Overflow)?;
+		proposal.abstain = proposal.abstain.checked_add(&abstain).ok_or(Error::<T>::Overflow)?;
+		proposal.voters = proposal.voters.checked_add(1).ok_or(Error::<T>::Overflow)?;
+		if proposal.status == ProposalStatus::Pending {
+			proposal.status = ProposalStatus::Active;
+		}
+
+		Ok(())
+	}
+
+	/// Whether a vote of this kind is on the winning side of a closed proposal. Abstentions and
+	/// split votes never win.
+	fn vote_won(proposal: &Proposal<T>, kind: VoteKind<BalanceOf<T>>) -> bool {
+		match (proposal.status, kind) {
+			(ProposalStatus::Passed | ProposalStatus::Enacted, VoteKind::Aye) => true,
+			(ProposalStatus::Rejected, VoteKind::Nay) => true,
+			_ => false,
 		}
+	}
+
+	/// Remove the votes delegated to a voter from the side of the proposal it voted on.
+	fn remove_delegated_votes(
+		proposal: &mut Proposal<T>,
+		kind: VoteKind<BalanceOf<T>>,
+		delegated: BalanceOf<T>,
+	) -> Result<(), DispatchError> {
+		let (ayes, nays, abstain) = Self::split_delegated_votes(kind, delegated);
+		proposal.ayes = proposal.ayes.checked_sub(&ayes).ok_or(Error::<T>::Underflow)?;
+		proposal.nays = proposal.nays.checked_sub(&nays).ok_or(Error::<T>::Underflow)?;
+		proposal.abstain = proposal.abstain.checked_sub(&abstain).ok_or(Error::<T>::Underflow)?;
+
+		Ok(())
+	}
+
+	/// Add the votes delegated to a voter to the side of the proposal it voted on.
+	fn add_delegated_votes(
+		proposal: &mut Proposal<T>,
+		kind: VoteKind<BalanceOf<T>>,
+		delegated: BalanceOf<T>,
+	) -> Result<(), DispatchError> {
+		let (ayes, nays, abstain) = Self::split_delegated_votes(kind, delegated);
+		proposal.ayes = proposal.ayes.checked_add(&ayes).ok_or(Error::<T>::Overflow)?;
+		proposal.nays = proposal.nays.checked_add(&nays).ok_or(Error::<T>::Overflow)?;
+		proposal.abstain = proposal.abstain.checked_add(&abstain).ok_or(Error::<T>::Overflow)?;
 
 		Ok(())
 	}
 
-	/// Freeze tokens if this is the highest amount to freeze.
-	/// Applies also if this is the first freeze on this account.
+	/// The ayes, nays and abstentions the votes delegated to a voter add to a tally when it
+	/// votes this kind. Split votes don't carry delegated votes.
+	fn split_delegated_votes(
+		kind: VoteKind<BalanceOf<T>>,
+		delegated: BalanceOf<T>,
+	) -> (BalanceOf<T>, BalanceOf<T>, BalanceOf<T>) {
+		match kind {
+			VoteKind::Split { .. } => (Zero::zero(), Zero::zero(), Zero::zero()),
+			kind => Self::split_votes(kind, delegated),
+		}
+	}
+
+	/// The ayes, nays and abstentions `votes` of this kind add to a tally. The ayes and nays of a
+	/// split vote are counted as `Conviction::None`.
+	fn split_votes(
+		kind: VoteKind<BalanceOf<T>>,
+		votes: BalanceOf<T>,
+	) -> (BalanceOf<T>, BalanceOf<T>, BalanceOf<T>) {
+		let zero = BalanceOf::<T>::zero();
+		match kind {
+			VoteKind::Aye => (votes, zero, zero),
+			VoteKind::Nay => (zero, votes, zero),
+			VoteKind::Abstain => (zero, zero, votes),
+			VoteKind::Split { aye, nay } =>
+				(Conviction::None.votes(aye), Conviction::None.votes(nay), zero),
+		}
+	}
+
+	/// Adds the vote to the voting history of the account and locks the tokens of its asset
+	/// the voting history requires.
 	/// It doesn't check if the account has enough tokens, so that check needs to be done
 	/// beforehand!
 	fn freeze(
 		who: T::AccountId,
 		user_vote: UserVoteInfo<T>,
 		new_voting_history: &mut BoundedVec<UserVoteInfo<T>, T::MaxVotes>,
-		required_tokens: BalanceOf<T>,
 	) -> Result<(), DispatchError> {
+		let asset = user_vote.asset.clone();
 		// Check if there are other votes for this account.
-		if let Some(mut voting_history) = VotingHistory::<T>::get(who.clone()) {
-			// Update his voting history.
-			voting_history.try_push(user_vote).map_err(|_| Error::<T>::TooManyVotes)?;
-			VotingHistory::<T>::insert(who.clone(), voting_history);
+		let voting_history = match VotingHistory::<T>::get(who.clone()) {
+			Some(mut voting_history) => {
+				// Update his voting history.
+				voting_history.try_push(user_vote).map_err(|_| Error::<T>::TooManyVotes)?;
+				voting_history
+			},
+			None => {
+				new_voting_history.try_push(user_vote).map_err(|_| Error::<T>::TooManyVotes)?;
+				new_voting_history.clone()
+			},
+		};
+		VotingHistory::<T>::insert(who.clone(), &voting_history);
+		Self::unfreeze(&who, &voting_history, &asset)?;
+
+		Ok(())
+	}
+
+	/// Sets the lock of an asset, or of the native token for `None`, of the specified account to
+	/// what the passed voting_history requires.
+	/// If there are no votes with the asset left, it will release the locked amount.
+	/// Otherwise, it will set the lock to the cost of the most expensive vote left, or hold the
+	/// cost of every vote with the native token separately in `LockMode::CumulativeHold`.
+	/// Returns the amount that stays locked.
+	pub(crate) fn unfreeze(
+		who: &T::AccountId,
+		voting_history: &BoundedVec<UserVoteInfo<T>, T::MaxVotes>,
+		asset: &Option<AssetIdOf<T>>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		let frozen = Self::required_freeze(voting_history, asset)?;
+		if asset.is_none() && T::LockMode::get() == LockMode::CumulativeHold {
+			Self::hold_votes(who, voting_history)?;
 		} else {
-			new_voting_history.try_push(user_vote).map_err(|_| Error::<T>::TooManyVotes)?;
-			VotingHistory::<T>::insert(who.clone(), new_voting_history.clone());
-			// The account has no other freezes.
-			T::NativeBalance::set_freeze(
-				&FreezeReason::AccountDeposit.into(),
-				&who,
-				required_tokens,
-			)?;
+			Self::set_frozen(who, asset, frozen)?;
 		}
-		// If this is the highest freeze until now, set this as the new freeze amount.
-		if required_tokens >
-			T::NativeBalance::balance_frozen(&FreezeReason::AccountDeposit.into(), &who)
-		{
-			T::NativeBalance::set_freeze(
-				&FreezeReason::AccountDeposit.into(),
-				&who,
-				required_tokens,
-			)?;
+
+		Ok(frozen)
+	}
+
+	/// The amount of an asset, or of the native token for `None`, that has to be locked to cover
+	/// every vote with it in the voting history. That is the cost of the most expensive vote, or
+	/// the sum of the costs in `LockMode::CumulativeHold`.
+	pub fn required_freeze(
+		voting_history: &[UserVoteInfo<T>],
+		asset: &Option<AssetIdOf<T>>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		let cumulative = T::LockMode::get() == LockMode::CumulativeHold;
+		voting_history.iter().filter(|item| item.asset == *asset).try_fold(
+			Zero::zero(),
+			|required: BalanceOf<T>, item| {
+				if cumulative {
+					Ok(required.checked_add(&item.amount).ok_or(Error::<T>::Overflow)?)
+				} else {
+					Ok(required.max(item.amount))
+				}
+			},
+		)
+	}
+
+	/// The tokens of an asset, or of the native token for `None`, locked by the votes of an
+	/// account.
+	fn frozen(who: &T::AccountId, asset: &Option<AssetIdOf<T>>) -> BalanceOf<T> {
+		match (asset, T::LockMode::get()) {
+			(Some(asset), _) => AssetFreezes::<T>::get(asset, who),
+			(None, LockMode::OverlappingFreeze) =>
+				T::NativeBalance::balance_frozen(&FreezeReason::AccountDeposit.into(), who),
+			(None, LockMode::CumulativeHold) =>
+				T::NativeBalance::balance_on_hold(&HoldReason::Vote.into(), who),
 		}
+	}
 
+	/// Freezes `amount` tokens of an asset, or of the native token for `None`, for the votes of an
+	/// account, thawing them if `amount` is zero. Native tokens still held by votes cast in
+	/// `LockMode::CumulativeHold`, before the lock mode changed, are released.
+	fn set_frozen(
+		who: &T::AccountId,
+		asset: &Option<AssetIdOf<T>>,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		let freeze_reason: T::RuntimeFreezeReason = FreezeReason::AccountDeposit.into();
+		match asset {
+			Some(asset) if amount.is_zero() => AssetFreezes::<T>::remove(asset, who),
+			Some(asset) => AssetFreezes::<T>::insert(asset, who, amount),
+			None => {
+				let hold_reason: T::RuntimeHoldReason = HoldReason::Vote.into();
+				if !T::NativeBalance::balance_on_hold(&hold_reason, who).is_zero() {
+					T::NativeBalance::release_all(&hold_reason, who, Precision::Exact)?;
+					let _ = VoteHolds::<T>::clear_prefix(who, u32::MAX, None);
+				}
+				if amount.is_zero() {
+					T::NativeBalance::thaw(&freeze_reason, who)?;
+				} else {
+					T::NativeBalance::set_freeze(&freeze_reason, who, amount)?;
+				}
+			},
+		}
 		Ok(())
 	}
 
-	/// Removes freezes from the specified account considering the passed voting_history.
-	/// If there s only one vote, it will thaw the frozen amount.
-	/// If there are multiple, it will set the freeze to the next max value.
-	fn unfreeze(
-		who: T::AccountId,
-		voting_history: &mut BoundedVec<UserVoteInfo<T>, T::MaxVotes>,
-	) -> Result<(), DispatchError> {
-		// Check if that was the only vote and free everything or just set the freeze to the
-		// next max value.
-		if let Some((_, max_freeze_proposal)) =
-			voting_history.iter().enumerate().max_by_key(|(_, item)| item.proposal_id)
-		{
-			T::NativeBalance::set_freeze(
-				&FreezeReason::AccountDeposit.into(),
-				&who,
-				max_freeze_proposal
-					.votes
-					.checked_mul(&max_freeze_proposal.votes)
-					.ok_or(Error::<T>::Overflow)?,
-			)?;
-		} else {
-			T::NativeBalance::thaw(&FreezeReason::AccountDeposit.into(), &who)?;
+	/// Holds the cost of every vote with the native token in the voting history of an account
+	/// in `LockMode::CumulativeHold`, each vote on its own, and releases exactly the amount held
+	/// for the votes that left the voting history. Native tokens still frozen by votes cast
+	/// before the lock mode changed are thawed.
+	fn hold_votes(
+		who: &T::AccountId,
+		voting_history: &BoundedVec<UserVoteInfo<T>, T::MaxVotes>,
+	) -> DispatchResult {
+		let freeze_reason: T::RuntimeFreezeReason = FreezeReason::AccountDeposit.into();
+		if !T::NativeBalance::balance_frozen(&freeze_reason, who).is_zero() {
+			T::NativeBalance::thaw(&freeze_reason, who)?;
+		}
+
+		let hold_reason: T::RuntimeHoldReason = HoldReason::Vote.into();
+		let native_votes: Vec<_> =
+			voting_history.iter().filter(|user_vote| user_vote.asset.is_none()).collect();
+		let holds: Vec<_> = VoteHolds::<T>::iter_prefix(who).collect();
+		for (proposal_id, held) in holds {
+			if !native_votes.iter().any(|user_vote| user_vote.proposal_id == proposal_id) {
+				T::NativeBalance::release(&hold_reason, who, held, Precision::Exact)?;
+				VoteHolds::<T>::remove(who, proposal_id);
+			}
+		}
+		for user_vote in native_votes {
+			let held = VoteHolds::<T>::get(who, user_vote.proposal_id);
+			if user_vote.amount > held {
+				T::NativeBalance::hold(&hold_reason, who, user_vote.amount.saturating_sub(held))?;
+			} else if user_vote.amount < held {
+				let released = held.saturating_sub(user_vote.amount);
+				T::NativeBalance::release(&hold_reason, who, released, Precision::Exact)?;
+			}
+			VoteHolds::<T>::insert(who, user_vote.proposal_id, user_vote.amount);
 		}
+		Ok(())
+	}
+
+	/// The asset the proposals of a track are voted on with, `None` for the native token and for
+	/// tracks that were removed.
+	fn track_asset(track: TrackId) -> Option<AssetIdOf<T>> {
+		T::Tracks::info(track).and_then(|track_info| track_info.asset)
+	}
 
+	/// The tokens of an asset, or of the native token for `None`, an account can freeze. Held
+	/// tokens and the existential deposit, or the min balance of the asset, can't be frozen.
+	/// Freezes overlap, so tokens already frozen by other votes or a delegation count.
+	fn freezable_balance(who: &T::AccountId, asset: &Option<AssetIdOf<T>>) -> BalanceOf<T> {
+		match asset {
+			Some(asset) => {
+				let balance =
+					<T::Assets as fungibles::Inspect<T::AccountId>>::balance(asset.clone(), who);
+				let min_balance =
+					<T::Assets as fungibles::Inspect<T::AccountId>>::minimum_balance(asset.clone());
+				balance.saturating_sub(min_balance)
+			},
+			None => <T::NativeBalance as fungible::Inspect<T::AccountId>>::reducible_balance(
+				who,
+				Preservation::Preserve,
+				Fortitude::Force,
+			),
+		}
+	}
+
+	/// The tokens of an asset, or of the native token for `None`, an account can lock for a new
+	/// vote. In `LockMode::CumulativeHold` the tokens locked by its other votes don't count.
+	fn lockable_balance(who: &T::AccountId, asset: &Option<AssetIdOf<T>>) -> BalanceOf<T> {
+		match (asset, T::LockMode::get()) {
+			(_, LockMode::OverlappingFreeze) => Self::freezable_balance(who, asset),
+			(Some(_), LockMode::CumulativeHold) =>
+				Self::freezable_balance(who, asset).saturating_sub(Self::frozen(who, asset)),
+			(None, LockMode::CumulativeHold) =>
+				<T::NativeBalance as fungible::Inspect<T::AccountId>>::reducible_balance(
+					who,
+					Preservation::Preserve,
+					Fortitude::Polite,
+				),
+		}
+	}
+
+	/// The tokens of an asset, or of the native token for `None`, an account is missing to lock
+	/// `amount` tokens for its vote on a proposal, zero if it can lock them. In
+	/// `LockMode::CumulativeHold` the tokens locked by its current vote on the proposal count, as
+	/// they are released when the vote changes.
+	fn lock_shortfall(
+		who: &T::AccountId,
+		proposal_id: T::ProposalId,
+		asset: &Option<AssetIdOf<T>>,
+		amount: BalanceOf<T>,
+	) -> BalanceOf<T> {
+		let mut available = Self::lockable_balance(who, asset);
+		if T::LockMode::get() == LockMode::CumulativeHold {
+			if let Some((index, voting_history)) =
+				Self::find_existing_vote(who.clone(), proposal_id)
+			{
+				if voting_history[index].asset == *asset {
+					available = available.saturating_add(voting_history[index].amount);
+				}
+			}
+		}
+		amount.saturating_sub(available)
+	}
+
+	/// Checks that an account can lock `amount` tokens of an asset, or of the native token for
+	/// `None`, missing `shortfall` of them. Accounts that don't own enough tokens get
+	/// `InsufficientFunds`, accounts whose tokens are held, locked or needed for their existential
+	/// deposit `InsufficientFreeBalance`.
+	fn ensure_affordable(
+		who: &T::AccountId,
+		asset: &Option<AssetIdOf<T>>,
+		amount: BalanceOf<T>,
+		shortfall: BalanceOf<T>,
+	) -> DispatchResult {
+		let total_balance = match asset {
+			Some(asset) =>
+				<T::Assets as fungibles::Inspect<T::AccountId>>::total_balance(asset.clone(), who),
+			None => <T::NativeBalance as fungible::Inspect<T::AccountId>>::total_balance(who),
+		};
+		ensure!(total_balance >= amount, Error::<T>::InsufficientFunds);
+		ensure!(shortfall.is_zero(), Error::<T>::InsufficientFreeBalance);
 		Ok(())
 	}
 
+	/// The votes delegated to an account that count for a vote with an asset. Delegations freeze
+	/// the native token, so only votes with the native token get them.
+	fn delegated_votes(who: &T::AccountId, asset: &Option<AssetIdOf<T>>) -> BalanceOf<T> {
+		if asset.is_some() {
+			Zero::zero()
+		} else {
+			DelegatedVotes::<T>::get(who)
+		}
+	}
+
 	// Checks if the proposal has ended.
-	// If the time has passed, it will update the proposal's end field to true.
 	fn proposal_ended(
-		start_block: BalanceOf<T>,
+		end_block: BalanceOf<T>,
 		current_block: BalanceOf<T>,
-		proposal: &mut Proposal<T>,
 	) -> Result<(), DispatchError> {
-		(start_block
-			.checked_add(&Self::convert_block_number_to_balance(T::ProposalDuration::get()))
-			.ok_or(Error::<T>::Overflow)
-			.and_then(|result| {
-				if result > current_block {
-					Err(Error::<T>::VotingPeriodNotOver)
-				} else {
-					// Close the proposal.
-					proposal.end = true;
-					Ok(())
-				}
-			}))?;
+		ensure!(end_block <= current_block, Error::<T>::VotingPeriodNotOver);
 
 		Ok(())
 	}
 
+	/// Closes a proposal whose voting period is over and declares the outcome of the vote.
+	/// Shared by `end_vote` and the hooks, so the caller must have checked that the voting
+	/// period is over.
+	fn close_proposal(proposal_id: T::ProposalId, mut proposal: Proposal<T>) {
+		let track_info = T::Tracks::info(proposal.track);
+		let participation = proposal.ayes.saturating_add(proposal.nays);
+		// Abstentions count for the turnout, but not for the approval.
+		let turnout = participation.saturating_add(proposal.abstain);
+		let electorate = RegisteredAccounts::<T>::count();
+		let (quorate, approved) = match &track_info {
+			Some(track_info) => (
+				proposal.voters >= track_info.min_voters,
+				proposal.ayes >= track_info.min_approval.mul_ceil(participation) &&
+					track_info.threshold.approved(
+						proposal.ayes,
+						proposal.nays,
+						proposal.voters,
+						electorate,
+					),
+			),
+			// Proposals on tracks that were removed only need more ayes than nays.
+			None => (true, proposal.ayes > proposal.nays),
+		};
+
+		// Calculate the outcome of the vote.
+		proposal.status = if turnout.is_zero() {
+			Self::deposit_event(Event::ProposalResultTie { proposal_id });
+			ProposalStatus::Expired
+		} else if !quorate {
+			Self::deposit_event(Event::ProposalResultNoQuorum { proposal_id });
+			ProposalStatus::NoQuorum
+		} else if approved {
+			Self::deposit_event(Event::ProposalResultAye { proposal_id });
+			let enacted = proposal
+				.call
+				.clone()
+				.map_or(false, |call| Self::enact(proposal_id, call).is_ok());
+			if enacted {
+				ProposalStatus::Enacted
+			} else {
+				ProposalStatus::Passed
+			}
+		} else if proposal.ayes == proposal.nays {
+			Self::deposit_event(Event::ProposalResultTie { proposal_id });
+			ProposalStatus::Tied
+		} else {
+			Self::deposit_event(Event::ProposalResultNay { proposal_id });
+			ProposalStatus::Rejected
+		};
+		proposal.closed_at = Some(Self::get_current_block_number());
+		// The scheduler requests the call itself when it is enacted.
+		for hash in Self::proposal_preimages(proposal_id, &proposal) {
+			T::Preimages::unrequest(&hash);
+		}
+
+		// Deposits of proposals on tracks that were removed are refunded.
+		let refund = track_info.map_or(true, |track_info| {
+			let scale = T::VotePrecision::get().saturating_mul(TALLY_SCALE);
+			turnout >= track_info.min_participation.saturating_mul(scale.into())
+		});
+		<ProposalPool<T>>::insert(proposal_id, proposal);
+		Self::settle_deposit(proposal_id, refund);
+	}
+
+	/// Refunds the deposit of a closed proposal to the proposer, or slashes it to `Slash`.
+	/// Proposals made without a deposit, such as the genesis ones, are skipped.
+	fn settle_deposit(proposal_id: T::ProposalId, refund: bool) {
+		let Some((who, amount)) = ProposalDeposits::<T>::take(proposal_id) else { return };
+		if amount.is_zero() {
+			return
+		}
+		let reason: T::RuntimeHoldReason = HoldReason::ProposalDeposit.into();
+		if refund {
+			let amount = T::NativeBalance::release(&reason, &who, amount, Precision::BestEffort)
+				.unwrap_or_default();
+			Self::deposit_event(Event::DepositRefunded { proposal_id, who, amount });
+		} else {
+			let (credit, _) =
+				<T::NativeBalance as fungible::hold::Balanced<_>>::slash(&reason, &who, amount);
+			let amount = credit.peek();
+			T::Slash::on_unbalanced(credit);
+			Self::deposit_event(Event::DepositSlashed { proposal_id, who, amount });
+		}
+	}
+
+	/// Ends a proposal without a result and marks it as cancelled. Its call is not enacted and
+	/// voters can claim their frozen tokens.
+	fn cancel(proposal_id: T::ProposalId, mut proposal: Proposal<T>) {
+		proposal.status = ProposalStatus::Cancelled;
+		proposal.closed_at = Some(Self::get_current_block_number());
+		for hash in Self::proposal_preimages(proposal_id, &proposal) {
+			T::Preimages::unrequest(&hash);
+		}
+		<ProposalPool<T>>::insert(proposal_id, proposal);
+	}
+
+	/// The preimages an open proposal requests: its description, if it was noted with the
+	/// preimage pallet, and its call, if it is too long to be stored inline.
+	pub(crate) fn proposal_preimages(
+		proposal_id: T::ProposalId,
+		proposal: &Proposal<T>,
+	) -> Vec<H256> {
+		let description = ProposalMetadataOf::<T>::get(proposal_id).and_then(|metadata| {
+			match metadata.description {
+				ProposalDescription::Preimage { hash, .. } => Some(hash),
+				ProposalDescription::Inline(_) => None,
+			}
+		});
+		let call = proposal.call.as_ref().and_then(|call| call.lookup_hash());
+		description.into_iter().chain(call).collect()
+	}
+
+	/// Closes a proposal scheduled by the hooks, unless it has already been closed with
+	/// `end_vote`.
+	fn close_if_ongoing(proposal_id: T::ProposalId) {
+		// Proposals of a ballot round are closed with the round.
+		if ProposalRound::<T>::contains_key(proposal_id) {
+			return
+		}
+		if let Some(proposal) = <ProposalPool<T>>::get(proposal_id) {
+			if proposal.status.is_open() {
+				Self::close_proposal(proposal_id, proposal);
+			}
+		}
+	}
+
+	/// Hands the call of a passed proposal to the scheduler, to be dispatched with the
+	/// enactment origin after the enactment period.
+	/// A failure to schedule doesn't revert the closing of the proposal, it is reported in the
+	/// `ProposalEnacted` event instead.
+	fn enact(proposal_id: T::ProposalId, call: BoundedCallOf<T>) -> DispatchResult {
+		let result = T::Scheduler::schedule_named(
+			(ENACTMENT_ID, proposal_id).blake2_256(),
+			DispatchTime::After(T::EnactmentPeriod::get()),
+			None,
+			63,
+			T::EnactmentOrigin::get(),
+			call,
+		)
+		.map(|_| ());
+
+		Self::deposit_event(Event::ProposalEnacted { proposal_id, result });
+		result
+	}
+
+	/// Information about a proposal, for the runtime API.
+	pub fn proposal_info(
+		proposal_id: T::ProposalId,
+	) -> Option<ProposalInfo<T::Hash, BalanceOf<T>, BlockNumberFor<T>>> {
+		let proposal = <ProposalPool<T>>::get(proposal_id)?;
+		let metadata = ProposalMetadataOf::<T>::get(proposal_id);
+		Some(ProposalInfo {
+			title: metadata.as_ref().map(|metadata| metadata.title.to_vec()).unwrap_or_default(),
+			text: metadata.and_then(|metadata| match metadata.description {
+				ProposalDescription::Inline(text) => Some(text.into_inner()),
+				ProposalDescription::Preimage { .. } => None,
+			}),
+			description: proposal.description,
+			start_block: proposal.start_block,
+			track: proposal.track,
+			end_block: proposal.end_block,
+			ayes: proposal.ayes,
+			nays: proposal.nays,
+			abstain: proposal.abstain,
+			voters: proposal.voters,
+			status: proposal.status,
+			closed_at: proposal.closed_at,
+			has_call: proposal.call.is_some(),
+		})
+	}
+
+	/// The votes cast on a proposal, for the runtime API.
+	pub fn tally(proposal_id: T::ProposalId) -> Option<Tally<BalanceOf<T>>> {
+		<ProposalPool<T>>::get(proposal_id)
+			.map(|proposal| Tally {
+				ayes: proposal.ayes,
+				nays: proposal.nays,
+				abstain: proposal.abstain,
+			})
+	}
+
+	/// The ids of the proposals that haven't been closed yet, for the runtime API.
+	pub fn active_proposals() -> Vec<T::ProposalId> {
+		<ProposalPool<T>>::iter()
+			.filter(|(_, proposal)| proposal.status.is_open())
+			.map(|(proposal_id, _)| proposal_id)
+			.collect()
+	}
+
+	/// The votes of a voter that haven't been claimed yet, for the runtime API.
+	pub fn voter_history(
+		who: &T::AccountId,
+	) -> Vec<VoteInfo<T::ProposalId, BalanceOf<T>, BlockNumberFor<T>>> {
+		VotingHistory::<T>::get(who)
+			.map(|voting_history| {
+				voting_history
+					.into_iter()
+					.map(|item| VoteInfo {
+						proposal_id: item.proposal_id,
+						kind: item.kind,
+						votes: item.votes,
+						amount: item.amount,
+						conviction: item.conviction,
+						unlock_at: item.unlock_at,
+					})
+					.collect()
+			})
+			.unwrap_or_default()
+	}
+
+	/// Whether the account is registered as a voter, for the runtime API.
+	pub fn is_registered(who: &T::AccountId) -> bool {
+		RegisteredAccounts::<T>::contains_key(who)
+	}
+
+	/// The tokens that have to be frozen to cast `votes` votes, or `None` on overflow.
+	pub fn vote_cost(votes: BalanceOf<T>) -> Option<BalanceOf<T>> {
+		votes.checked_mul(&votes)
+	}
+
+	/// The tokens an account is missing to cast `votes` votes on a proposal, zero if it can
+	/// freeze them, or `None` if the proposal doesn't exist or on overflow. A vote that fails with
+	/// `InsufficientFreeBalance` misses exactly that amount.
+	pub fn vote_shortfall(
+		who: &T::AccountId,
+		proposal_id: T::ProposalId,
+		votes: BalanceOf<T>,
+	) -> Option<BalanceOf<T>> {
+		let proposal = <ProposalPool<T>>::get(proposal_id)?;
+		let cost = Self::vote_cost(votes)?;
+		let asset = Self::track_asset(proposal.track);
+		Some(Self::lock_shortfall(who, proposal_id, &asset, cost))
+	}
+
+	/// The native tokens of an account frozen, or held in `LockMode::CumulativeHold`, by its
+	/// votes.
+	pub fn frozen_balance(who: &T::AccountId) -> BalanceOf<T> {
+		Self::frozen(who, &None)
+	}
+
 	// Checks if there is a vote for this proposal and returns information about it.
 	// If no vote exists, returns None.
 	fn find_existing_vote(
@@ -630,3 +2767,17 @@ impl<T: Config> Pallet<T> {
 		None
 	}
 }
+
+/// Reports the tokens of an asset frozen by the votes of an account to the assets pallet, which
+/// keeps them from being transferred.
+impl<T: Config> pallet_assets::FrozenBalance<AssetIdOf<T>, T::AccountId, BalanceOf<T>>
+	for Pallet<T>
+{
+	fn frozen_balance(asset: AssetIdOf<T>, who: &T::AccountId) -> Option<BalanceOf<T>> {
+		let frozen = AssetFreezes::<T>::get(asset, who);
+		(!frozen.is_zero()).then_some(frozen)
+	}
+
+	// The freeze is kept, as the votes are still there if the account is created again.
+	fn died(_asset: AssetIdOf<T>, _who: &T::AccountId) {}
+}
@@ -0,0 +1,19 @@
+//! How the tokens of the votes of an account are locked.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+
+/// How the tokens of the votes of an account are locked.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug, MaxEncodedLen, TypeInfo)]
+pub enum LockMode {
+	/// Every vote freezes the square of its votes and the freezes overlap, so only the most
+	/// expensive vote of the account is frozen. 100 tokens are enough for 10 votes on every open
+	/// proposal.
+	#[codec(index = 0)]
+	OverlappingFreeze,
+	/// The square of the votes of every vote is held from the account on its own and the holds add
+	/// up, so every vote pays its own cost. 100 tokens are enough for 10 votes on a single
+	/// proposal. Assets can't be held, so votes with an asset freeze the sum instead.
+	#[codec(index = 1)]
+	CumulativeHold,
+}
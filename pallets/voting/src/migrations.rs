@@ -0,0 +1,1061 @@
+//! Storage migrations for pallet-voting.
+
+use super::*;
+use core::cmp::Ordering;
+use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+pub mod v1 {
+	use super::{v2::OldProposal, v7::VotingHistory, *};
+
+	/// The proposal as stored up to storage version 0, before proposals had a call.
+	#[derive(Encode, Decode)]
+	pub struct ProposalV0<T: Config> {
+		pub description: T::Hash,
+		pub start_block: BlockNumberFor<T>,
+		pub ayes: BalanceOf<T>,
+		pub nays: BalanceOf<T>,
+		pub end: bool,
+	}
+
+	/// The proposals, as stored in storage version 1.
+	#[frame_support::storage_alias]
+	pub type ProposalPool<T: Config> =
+		StorageMap<Pallet<T>, Blake2_128Concat, <T as Config>::ProposalId, OldProposal<T>>;
+
+	/// Adds an empty call to every proposal and recomputes the freeze of every voter from the
+	/// voting history.
+	///
+	/// Up to storage version 0 proposals had no call to enact, and the freeze was set from the
+	/// vote on the highest proposal id instead of the most expensive vote, so accounts could end
+	/// up with less frozen than their outstanding votes cost. Empty voting histories are removed.
+	pub struct MigrateToV1<T>(sp_std::marker::PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, frame_support::sp_runtime::TryRuntimeError> {
+			Ok((ProposalPool::<T>::iter_keys().count() as u32).encode())
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 0 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut reads = 1u64;
+			let mut writes = 1u64;
+			ProposalPool::<T>::translate::<ProposalV0<T>, _>(|_, old| {
+				reads += 1;
+				writes += 1;
+				Some(OldProposal {
+					description: old.description,
+					start_block: old.start_block,
+					ayes: old.ayes,
+					nays: old.nays,
+					end: old.end,
+					call: None,
+				})
+			});
+
+			let voters = VotingHistory::<T>::iter_keys().collect::<Vec<_>>();
+			for who in voters {
+				reads += 1;
+				let Some(voting_history) = VotingHistory::<T>::get(&who) else { continue };
+				if voting_history.is_empty() {
+					VotingHistory::<T>::remove(&who);
+					writes += 1;
+				}
+				let voting_history = v11::migrate_voting_history(v9::migrate_voting_history(
+					v8::migrate_voting_history(v7::migrate_voting_history(voting_history)),
+				));
+				// The freeze of accounts whose votes overflow is left as it is.
+				if Pallet::<T>::unfreeze(&who, &voting_history, &None).is_ok() {
+					reads += 1;
+					writes += 1;
+				}
+			}
+
+			StorageVersion::new(1).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), frame_support::sp_runtime::TryRuntimeError> {
+			ensure!(Pallet::<T>::on_chain_storage_version() == 1, "storage version not updated");
+			let count = u32::decode(&mut &state[..]).map_err(|_| "invalid pre upgrade state")?;
+			ensure!(
+				ProposalPool::<T>::iter_values().count() as u32 == count,
+				"proposals lost in the migration"
+			);
+			for (who, voting_history) in VotingHistory::<T>::iter() {
+				ensure!(!voting_history.is_empty(), "empty voting history left");
+				let voting_history = v11::migrate_voting_history(v9::migrate_voting_history(
+					v8::migrate_voting_history(v7::migrate_voting_history(voting_history)),
+				));
+				let required = Pallet::<T>::required_freeze(&voting_history, &None)?;
+				ensure!(
+					T::NativeBalance::balance_frozen(&FreezeReason::AccountDeposit.into(), &who) ==
+						required,
+					"freeze does not match the voting history"
+				);
+			}
+			Ok(())
+		}
+	}
+}
+
+pub mod v2 {
+	use super::*;
+
+	/// The proposal as stored up to storage version 1.
+	#[derive(Encode, Decode)]
+	pub struct OldProposal<T: Config> {
+		pub description: T::Hash,
+		pub start_block: BlockNumberFor<T>,
+		pub ayes: BalanceOf<T>,
+		pub nays: BalanceOf<T>,
+		pub end: bool,
+		pub call: Option<BoundedCallOf<T>>,
+	}
+
+	/// The proposal as stored in storage version 2.
+	#[derive(Encode, Decode)]
+	pub struct ProposalV2<T: Config> {
+		pub description: T::Hash,
+		pub start_block: BlockNumberFor<T>,
+		pub ayes: BalanceOf<T>,
+		pub nays: BalanceOf<T>,
+		pub status: ProposalStatus,
+		pub closed_at: Option<BlockNumberFor<T>>,
+		pub call: Option<BoundedCallOf<T>>,
+	}
+
+	/// The proposals, as stored in storage version 2.
+	#[frame_support::storage_alias]
+	pub type ProposalPool<T: Config> =
+		StorageMap<Pallet<T>, Blake2_128Concat, <T as Config>::ProposalId, ProposalV2<T>>;
+
+	/// The proposals that were withdrawn, cancelled or killed, up to storage version 1.
+	#[frame_support::storage_alias]
+	pub type CancelledProposals<T: Config> =
+		StorageMap<Pallet<T>, Blake2_128Concat, <T as Config>::ProposalId, ()>;
+
+	/// Replaces the `end` flag of every proposal with its status.
+	///
+	/// Open proposals are `Pending` or `Active` depending on whether they have votes. Closed
+	/// proposals get the status matching their tally, or `Cancelled` if they were recorded in
+	/// `CancelledProposals`, which is removed. Passed proposals with a call were handed to the
+	/// scheduler when they were closed, so they are `Enacted`. The block the proposals were closed
+	/// in isn't known and is left empty.
+	pub struct MigrateToV2<T>(sp_std::marker::PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, frame_support::sp_runtime::TryRuntimeError> {
+			Ok((ProposalPool::<T>::iter_keys().count() as u32).encode())
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 1 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut translated = 0u64;
+			ProposalPool::<T>::translate::<OldProposal<T>, _>(|proposal_id, old| {
+				translated += 1;
+				let cancelled = CancelledProposals::<T>::take(proposal_id).is_some();
+				let status = if !old.end {
+					if old.ayes.is_zero() && old.nays.is_zero() {
+						ProposalStatus::Pending
+					} else {
+						ProposalStatus::Active
+					}
+				} else if cancelled {
+					ProposalStatus::Cancelled
+				} else {
+					match old.ayes.cmp(&old.nays) {
+						Ordering::Greater if old.call.is_some() => ProposalStatus::Enacted,
+						Ordering::Greater => ProposalStatus::Passed,
+						Ordering::Less => ProposalStatus::Rejected,
+						Ordering::Equal if old.ayes.is_zero() => ProposalStatus::Expired,
+						Ordering::Equal => ProposalStatus::Tied,
+					}
+				};
+				Some(ProposalV2 {
+					description: old.description,
+					start_block: old.start_block,
+					ayes: old.ayes,
+					nays: old.nays,
+					status,
+					closed_at: None,
+					call: old.call,
+				})
+			});
+			// Entries of proposals that no longer exist.
+			let _ = CancelledProposals::<T>::clear(u32::MAX, None);
+
+			StorageVersion::new(2).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(2 * translated + 2, 2 * translated + 2)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), frame_support::sp_runtime::TryRuntimeError> {
+			ensure!(Pallet::<T>::on_chain_storage_version() == 2, "storage version not updated");
+			let count = u32::decode(&mut &state[..]).map_err(|_| "invalid pre upgrade state")?;
+			ensure!(
+				ProposalPool::<T>::iter_values().count() as u32 == count,
+				"proposals lost in the migration"
+			);
+			ensure!(
+				CancelledProposals::<T>::iter_keys().next().is_none(),
+				"cancelled proposals left"
+			);
+			Ok(())
+		}
+	}
+}
+
+pub mod v3 {
+	use super::{v2::ProposalV2, *};
+
+	/// The proposal as stored in storage version 3.
+	#[derive(Encode, Decode)]
+	pub struct ProposalV3<T: Config> {
+		pub description: T::Hash,
+		pub track: TrackId,
+		pub start_block: BlockNumberFor<T>,
+		pub end_block: BlockNumberFor<T>,
+		pub ayes: BalanceOf<T>,
+		pub nays: BalanceOf<T>,
+		pub status: ProposalStatus,
+		pub closed_at: Option<BlockNumberFor<T>>,
+		pub call: Option<BoundedCallOf<T>>,
+	}
+
+	/// The proposals, as stored in storage version 3.
+	#[frame_support::storage_alias]
+	pub type ProposalPool<T: Config> =
+		StorageMap<Pallet<T>, Blake2_128Concat, <T as Config>::ProposalId, ProposalV3<T>>;
+
+	/// Puts every proposal on a track and stores the end of its voting period.
+	///
+	/// Proposals made before tracks existed are put on the first track of `T::Tracks`. Their
+	/// voting period keeps its start and takes the duration of that track.
+	pub struct MigrateToV3<T>(sp_std::marker::PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV3<T> {
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, frame_support::sp_runtime::TryRuntimeError> {
+			Ok((ProposalPool::<T>::iter_keys().count() as u32).encode())
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 2 {
+				return T::DbWeight::get().reads(1)
+			}
+			// Without a track the proposals are left as they are, but the storage version is still
+			// bumped so that the later migrations run.
+			let Some((track, track_info)) = T::Tracks::tracks().into_iter().next() else {
+				StorageVersion::new(3).put::<Pallet<T>>();
+				return T::DbWeight::get().reads_writes(1, 1)
+			};
+
+			let mut translated = 0u64;
+			ProposalPool::<T>::translate::<ProposalV2<T>, _>(|_, old| {
+				translated += 1;
+				Some(ProposalV3 {
+					description: old.description,
+					track,
+					start_block: old.start_block,
+					end_block: old.start_block.saturating_add(track_info.duration),
+					ayes: old.ayes,
+					nays: old.nays,
+					status: old.status,
+					closed_at: old.closed_at,
+					call: old.call,
+				})
+			});
+
+			StorageVersion::new(3).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), frame_support::sp_runtime::TryRuntimeError> {
+			ensure!(Pallet::<T>::on_chain_storage_version() == 3, "storage version not updated");
+			let count = u32::decode(&mut &state[..]).map_err(|_| "invalid pre upgrade state")?;
+			ensure!(
+				ProposalPool::<T>::iter_values().count() as u32 == count,
+				"proposals lost in the migration"
+			);
+			Ok(())
+		}
+	}
+}
+
+pub mod v4 {
+	use super::{v3::ProposalV3, v7::VotingHistory, *};
+	use sp_std::collections::btree_map::BTreeMap;
+
+	/// The proposal as stored in storage versions 4 and 5.
+	#[derive(Encode, Decode)]
+	pub struct ProposalV4<T: Config> {
+		pub description: T::Hash,
+		pub track: TrackId,
+		pub start_block: BlockNumberFor<T>,
+		pub end_block: BlockNumberFor<T>,
+		pub ayes: BalanceOf<T>,
+		pub nays: BalanceOf<T>,
+		pub voters: u32,
+		pub status: ProposalStatus,
+		pub closed_at: Option<BlockNumberFor<T>>,
+		pub call: Option<BoundedCallOf<T>>,
+	}
+
+	/// The proposals, as stored in storage versions 4 and 5.
+	#[frame_support::storage_alias]
+	pub type ProposalPool<T: Config> =
+		StorageMap<Pallet<T>, Blake2_128Concat, <T as Config>::ProposalId, ProposalV4<T>>;
+
+	/// Stores the number of distinct voters of every proposal, counted for the quorum.
+	///
+	/// The voters are counted from the voting histories. Ballots only record the voice credits
+	/// they cost, so the proposals of ballot rounds get the number of ballots cast in their round.
+	pub struct MigrateToV4<T>(sp_std::marker::PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV4<T> {
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, frame_support::sp_runtime::TryRuntimeError> {
+			Ok((v3::ProposalPool::<T>::iter_keys().count() as u32).encode())
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 3 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut reads = 1u64;
+			let mut voters = BTreeMap::<T::ProposalId, u32>::new();
+			for voting_history in VotingHistory::<T>::iter_values() {
+				reads += 1;
+				for user_vote in voting_history {
+					*voters.entry(user_vote.proposal_id).or_default() += 1;
+				}
+			}
+			for (proposal_id, round) in ProposalRound::<T>::iter() {
+				reads += 1;
+				voters.insert(proposal_id, Ballots::<T>::iter_prefix(round).count() as u32);
+			}
+
+			let mut translated = 0u64;
+			ProposalPool::<T>::translate::<ProposalV3<T>, _>(|proposal_id, old| {
+				translated += 1;
+				Some(ProposalV4 {
+					description: old.description,
+					track: old.track,
+					start_block: old.start_block,
+					end_block: old.end_block,
+					ayes: old.ayes,
+					nays: old.nays,
+					voters: voters.get(&proposal_id).copied().unwrap_or_default(),
+					status: old.status,
+					closed_at: old.closed_at,
+					call: old.call,
+				})
+			});
+
+			StorageVersion::new(4).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(reads + translated, translated + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), frame_support::sp_runtime::TryRuntimeError> {
+			ensure!(Pallet::<T>::on_chain_storage_version() == 4, "storage version not updated");
+			let count = u32::decode(&mut &state[..]).map_err(|_| "invalid pre upgrade state")?;
+			ensure!(
+				ProposalPool::<T>::iter_values().count() as u32 == count,
+				"proposals lost in the migration"
+			);
+			Ok(())
+		}
+	}
+}
+
+pub mod v5 {
+	use super::*;
+
+	/// Counts the registered voters, the electorate of the vote thresholds.
+	pub struct MigrateToV5<T>(sp_std::marker::PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV5<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 4 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let voters = RegisteredAccounts::<T>::initialize_counter();
+
+			StorageVersion::new(5).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(voters as u64 + 1, 2)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_: Vec<u8>) -> Result<(), frame_support::sp_runtime::TryRuntimeError> {
+			ensure!(Pallet::<T>::on_chain_storage_version() == 5, "storage version not updated");
+			ensure!(
+				RegisteredAccounts::<T>::iter_keys().count() as u32 ==
+					RegisteredAccounts::<T>::count(),
+				"registered voters miscounted"
+			);
+			Ok(())
+		}
+	}
+}
+
+pub mod v6 {
+	use super::{v4::ProposalV4, *};
+
+	/// Adds the abstentions to the tally of every proposal.
+	///
+	/// Votes cast before abstaining was possible are ayes or nays, so every tally starts without
+	/// abstentions. The votes in the voting histories keep their encoding.
+	pub struct MigrateToV6<T>(sp_std::marker::PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV6<T> {
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, frame_support::sp_runtime::TryRuntimeError> {
+			Ok((v4::ProposalPool::<T>::iter_keys().count() as u32).encode())
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 5 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut translated = 0u64;
+			ProposalPool::<T>::translate::<ProposalV4<T>, _>(|_, old| {
+				translated += 1;
+				Some(Proposal {
+					description: old.description,
+					track: old.track,
+					start_block: old.start_block,
+					end_block: old.end_block,
+					ayes: old.ayes,
+					nays: old.nays,
+					abstain: Zero::zero(),
+					voters: old.voters,
+					status: old.status,
+					closed_at: old.closed_at,
+					call: old.call,
+				})
+			});
+
+			StorageVersion::new(6).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), frame_support::sp_runtime::TryRuntimeError> {
+			ensure!(Pallet::<T>::on_chain_storage_version() == 6, "storage version not updated");
+			let count = u32::decode(&mut &state[..]).map_err(|_| "invalid pre upgrade state")?;
+			ensure!(
+				ProposalPool::<T>::iter_values().count() as u32 == count,
+				"proposals lost in the migration"
+			);
+			Ok(())
+		}
+	}
+}
+
+pub mod v7 {
+	use super::{v8::UserVoteInfoV7, *};
+
+	/// A vote as stored up to storage version 6.
+	#[derive(Encode, Decode)]
+	pub struct UserVoteInfoV6<T: Config> {
+		pub proposal_id: T::ProposalId,
+		pub kind: VoteKind<BalanceOf<T>>,
+		pub votes: BalanceOf<T>,
+	}
+
+	impl<T: Config> UserVoteInfoV6<T> {
+		/// The vote without a conviction. It was never locked, so it can be claimed right away.
+		pub fn migrate(self) -> UserVoteInfoV7<T> {
+			UserVoteInfoV7 {
+				proposal_id: self.proposal_id,
+				kind: self.kind,
+				votes: self.votes,
+				conviction: None,
+				unlock_at: Zero::zero(),
+			}
+		}
+	}
+
+	/// The voting histories, as stored up to storage version 6.
+	#[frame_support::storage_alias]
+	pub type VotingHistory<T: Config> = StorageMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		<T as frame_system::Config>::AccountId,
+		BoundedVec<UserVoteInfoV6<T>, <T as Config>::MaxVotes>,
+	>;
+
+	/// Migrates every vote of a voting history.
+	pub fn migrate_voting_history<T: Config>(
+		voting_history: BoundedVec<UserVoteInfoV6<T>, T::MaxVotes>,
+	) -> BoundedVec<UserVoteInfoV7<T>, T::MaxVotes> {
+		BoundedVec::truncate_from(
+			voting_history.into_iter().map(UserVoteInfoV6::migrate).collect::<Vec<_>>(),
+		)
+	}
+
+	/// Adds the conviction and unlock block to every vote.
+	///
+	/// Votes cast before convictions existed are plain votes, counted once and never locked.
+	pub struct MigrateToV7<T>(sp_std::marker::PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV7<T> {
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, frame_support::sp_runtime::TryRuntimeError> {
+			let votes: u32 = VotingHistory::<T>::iter_values()
+				.map(|voting_history| voting_history.len() as u32)
+				.sum();
+			Ok(votes.encode())
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 6 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut translated = 0u64;
+			v8::VotingHistory::<T>::translate::<BoundedVec<UserVoteInfoV6<T>, T::MaxVotes>, _>(
+				|_, voting_history| {
+					translated += 1;
+					Some(migrate_voting_history(voting_history))
+				},
+			);
+
+			StorageVersion::new(7).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), frame_support::sp_runtime::TryRuntimeError> {
+			ensure!(Pallet::<T>::on_chain_storage_version() == 7, "storage version not updated");
+			let count = u32::decode(&mut &state[..]).map_err(|_| "invalid pre upgrade state")?;
+			let votes: u32 = v8::VotingHistory::<T>::iter_values()
+				.map(|voting_history| voting_history.len() as u32)
+				.sum();
+			ensure!(votes == count, "votes lost in the migration");
+			Ok(())
+		}
+	}
+}
+
+pub mod v8 {
+	use super::*;
+
+	/// A vote as stored up to storage version 7.
+	#[derive(Encode, Decode)]
+	pub struct UserVoteInfoV7<T: Config> {
+		pub proposal_id: T::ProposalId,
+		pub kind: VoteKind<BalanceOf<T>>,
+		pub votes: BalanceOf<T>,
+		pub conviction: Option<Conviction>,
+		pub unlock_at: BlockNumberFor<T>,
+	}
+
+	impl<T: Config> UserVoteInfoV7<T> {
+		/// The vote with the native token, the only one votes could freeze.
+		pub fn migrate(self) -> v9::UserVoteInfoV8<T> {
+			v9::UserVoteInfoV8 {
+				proposal_id: self.proposal_id,
+				kind: self.kind,
+				votes: self.votes,
+				conviction: self.conviction,
+				unlock_at: self.unlock_at,
+				asset: None,
+			}
+		}
+	}
+
+	/// The voting histories, as stored up to storage version 7.
+	#[frame_support::storage_alias]
+	pub type VotingHistory<T: Config> = StorageMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		<T as frame_system::Config>::AccountId,
+		BoundedVec<UserVoteInfoV7<T>, <T as Config>::MaxVotes>,
+	>;
+
+	/// Migrates every vote of a voting history.
+	pub fn migrate_voting_history<T: Config>(
+		voting_history: BoundedVec<UserVoteInfoV7<T>, T::MaxVotes>,
+	) -> BoundedVec<v9::UserVoteInfoV8<T>, T::MaxVotes> {
+		BoundedVec::truncate_from(
+			voting_history.into_iter().map(UserVoteInfoV7::migrate).collect::<Vec<_>>(),
+		)
+	}
+
+	/// Adds the asset to every vote.
+	///
+	/// Votes cast before tracks could vote with an asset froze the native token.
+	pub struct MigrateToV8<T>(sp_std::marker::PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV8<T> {
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, frame_support::sp_runtime::TryRuntimeError> {
+			let votes: u32 = VotingHistory::<T>::iter_values()
+				.map(|voting_history| voting_history.len() as u32)
+				.sum();
+			Ok(votes.encode())
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 7 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut translated = 0u64;
+			v9::VotingHistory::<T>::translate::<BoundedVec<UserVoteInfoV7<T>, T::MaxVotes>, _>(
+				|_, voting_history| {
+					translated += 1;
+					Some(migrate_voting_history(voting_history))
+				},
+			);
+
+			StorageVersion::new(8).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), frame_support::sp_runtime::TryRuntimeError> {
+			ensure!(Pallet::<T>::on_chain_storage_version() == 8, "storage version not updated");
+			let count = u32::decode(&mut &state[..]).map_err(|_| "invalid pre upgrade state")?;
+			let votes: u32 = v9::VotingHistory::<T>::iter_values()
+				.map(|voting_history| voting_history.len() as u32)
+				.sum();
+			ensure!(votes == count, "votes lost in the migration");
+			Ok(())
+		}
+	}
+}
+
+pub mod v9 {
+	use super::*;
+
+	/// A vote as stored up to storage version 8.
+	#[derive(Encode, Decode)]
+	pub struct UserVoteInfoV8<T: Config> {
+		pub proposal_id: T::ProposalId,
+		pub kind: VoteKind<BalanceOf<T>>,
+		pub votes: BalanceOf<T>,
+		pub conviction: Option<Conviction>,
+		pub unlock_at: BlockNumberFor<T>,
+		pub asset: Option<AssetIdOf<T>>,
+	}
+
+	impl<T: Config> UserVoteInfoV8<T> {
+		/// The vote with the tokens it locked, the square of its whole votes.
+		pub fn migrate(self) -> v11::UserVoteInfoV10<T> {
+			v11::UserVoteInfoV10 {
+				proposal_id: self.proposal_id,
+				kind: self.kind,
+				votes: self.votes,
+				amount: self.votes.saturating_mul(self.votes),
+				conviction: self.conviction,
+				unlock_at: self.unlock_at,
+				asset: self.asset,
+			}
+		}
+	}
+
+	/// The voting histories, as stored up to storage version 8.
+	#[frame_support::storage_alias]
+	pub type VotingHistory<T: Config> = StorageMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		<T as frame_system::Config>::AccountId,
+		BoundedVec<UserVoteInfoV8<T>, <T as Config>::MaxVotes>,
+	>;
+
+	/// Migrates every vote of a voting history.
+	pub fn migrate_voting_history<T: Config>(
+		voting_history: BoundedVec<UserVoteInfoV8<T>, T::MaxVotes>,
+	) -> BoundedVec<v11::UserVoteInfoV10<T>, T::MaxVotes> {
+		BoundedVec::truncate_from(
+			voting_history.into_iter().map(UserVoteInfoV8::migrate).collect::<Vec<_>>(),
+		)
+	}
+
+	/// Adds the locked amount to every vote.
+	///
+	/// Votes cast before `vote_with_amount` locked the square of their votes, which were whole
+	/// votes. The migration keeps them as they are, so it has to run with a `VotePrecision` of 1.
+	pub struct MigrateToV9<T>(sp_std::marker::PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV9<T> {
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, frame_support::sp_runtime::TryRuntimeError> {
+			let votes: u32 = VotingHistory::<T>::iter_values()
+				.map(|voting_history| voting_history.len() as u32)
+				.sum();
+			Ok(votes.encode())
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 8 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut translated = 0u64;
+			v11::VotingHistory::<T>::translate::<BoundedVec<UserVoteInfoV8<T>, T::MaxVotes>, _>(
+				|_, voting_history| {
+					translated += 1;
+					Some(migrate_voting_history(voting_history))
+				},
+			);
+
+			StorageVersion::new(9).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), frame_support::sp_runtime::TryRuntimeError> {
+			ensure!(Pallet::<T>::on_chain_storage_version() == 9, "storage version not updated");
+			let count = u32::decode(&mut &state[..]).map_err(|_| "invalid pre upgrade state")?;
+			let votes: u32 = v11::VotingHistory::<T>::iter_values()
+				.map(|voting_history| voting_history.len() as u32)
+				.sum();
+			ensure!(votes == count, "votes lost in the migration");
+			Ok(())
+		}
+	}
+}
+
+pub mod v10 {
+	use super::*;
+
+	/// A delegation as stored up to storage version 9.
+	#[derive(Encode, Decode)]
+	pub struct DelegationV9<T: Config> {
+		pub target: T::AccountId,
+		pub conviction: Conviction,
+		pub balance: BalanceOf<T>,
+		pub votes: BalanceOf<T>,
+		pub unlock_at: BlockNumberFor<T>,
+	}
+
+	/// The delegations, as stored up to storage version 9.
+	#[frame_support::storage_alias]
+	pub type Delegations<T: Config> = StorageMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		<T as frame_system::Config>::AccountId,
+		DelegationV9<T>,
+	>;
+
+	/// Removes the unlock block from every delegation.
+	///
+	/// Up to storage version 9 the lock periods of a delegation started when it was made. They
+	/// now start when it is removed, so the delegations of the accounts are kept as they are and
+	/// their tokens are locked from the moment they undelegate.
+	pub struct MigrateToV10<T>(sp_std::marker::PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV10<T> {
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, frame_support::sp_runtime::TryRuntimeError> {
+			Ok((Delegations::<T>::iter_keys().count() as u32).encode())
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 9 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut translated = 0u64;
+			crate::Delegations::<T>::translate::<DelegationV9<T>, _>(|_, old| {
+				translated += 1;
+				Some(Delegation {
+					target: old.target,
+					conviction: old.conviction,
+					balance: old.balance,
+					votes: old.votes,
+				})
+			});
+
+			StorageVersion::new(10).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), frame_support::sp_runtime::TryRuntimeError> {
+			ensure!(Pallet::<T>::on_chain_storage_version() == 10, "storage version not updated");
+			let count = u32::decode(&mut &state[..]).map_err(|_| "invalid pre upgrade state")?;
+			ensure!(
+				crate::Delegations::<T>::iter_values().count() as u32 == count,
+				"delegations lost in the migration"
+			);
+			Ok(())
+		}
+	}
+}
+
+pub mod v11 {
+	use super::*;
+	use crate::conviction::TALLY_SCALE;
+	use sp_std::collections::btree_set::BTreeSet;
+
+	/// A vote as stored up to storage version 10.
+	#[derive(Encode, Decode)]
+	pub struct UserVoteInfoV10<T: Config> {
+		pub proposal_id: T::ProposalId,
+		pub kind: VoteKind<BalanceOf<T>>,
+		pub votes: BalanceOf<T>,
+		pub amount: BalanceOf<T>,
+		pub conviction: Option<Conviction>,
+		pub unlock_at: BlockNumberFor<T>,
+		pub asset: Option<AssetIdOf<T>>,
+	}
+
+	impl<T: Config> UserVoteInfoV10<T> {
+		/// The vote with votes cast without a conviction stored as `Conviction::None`.
+		pub fn migrate(self) -> UserVoteInfo<T> {
+			UserVoteInfo {
+				proposal_id: self.proposal_id,
+				kind: self.kind,
+				votes: self.votes,
+				amount: self.amount,
+				conviction: self.conviction.unwrap_or(Conviction::None),
+				unlock_at: self.unlock_at,
+				asset: self.asset,
+			}
+		}
+	}
+
+	/// The voting histories, as stored in storage versions 9 and 10.
+	#[frame_support::storage_alias]
+	pub type VotingHistory<T: Config> = StorageMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		<T as frame_system::Config>::AccountId,
+		BoundedVec<UserVoteInfoV10<T>, <T as Config>::MaxVotes>,
+	>;
+
+	/// Migrates every vote of a voting history.
+	pub fn migrate_voting_history<T: Config>(
+		voting_history: BoundedVec<UserVoteInfoV10<T>, T::MaxVotes>,
+	) -> BoundedVec<UserVoteInfo<T>, T::MaxVotes> {
+		BoundedVec::truncate_from(
+			voting_history.into_iter().map(UserVoteInfoV10::migrate).collect::<Vec<_>>(),
+		)
+	}
+
+	/// Stores every vote cast without a conviction as `Conviction::None` and counts the tallies
+	/// in tenths of a vote.
+	///
+	/// Up to storage version 10 votes cast without a conviction, abstentions, split votes and
+	/// ballots were counted once, and tallies and delegations counted whole votes, rounding
+	/// `Conviction::None` down. The delegations are recomputed from their tokens and the tallies
+	/// of the open proposals are recounted from their votes at the new scale, so no vote is
+	/// rounded down. The ballots of open rounds now count as `Conviction::None` and are kept as
+	/// they are. Closed proposals keep their result, with their tally counted in tenths.
+	pub struct MigrateToV11<T>(sp_std::marker::PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV11<T> {
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, frame_support::sp_runtime::TryRuntimeError> {
+			let votes: u32 = VotingHistory::<T>::iter_values()
+				.map(|voting_history| voting_history.len() as u32)
+				.sum();
+			Ok(votes.encode())
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 10 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut reads = 1u64;
+			let mut writes = 1u64;
+			let _ = crate::DelegatedVotes::<T>::clear(u32::MAX, None);
+			crate::Delegations::<T>::translate::<Delegation<T>, _>(|_, mut delegation| {
+				reads += 2;
+				writes += 2;
+				if let Ok(votes) = Pallet::<T>::amount_votes(delegation.balance) {
+					delegation.votes = delegation.conviction.votes(votes);
+				}
+				crate::DelegatedVotes::<T>::mutate(&delegation.target, |delegated| {
+					*delegated = delegated.saturating_add(delegation.votes)
+				});
+				Some(delegation)
+			});
+
+			let scale: BalanceOf<T> = TALLY_SCALE.into();
+			let mut recounted = BTreeSet::new();
+			crate::ProposalPool::<T>::translate::<Proposal<T>, _>(|proposal_id, mut proposal| {
+				reads += 2;
+				writes += 1;
+				if !proposal.status.is_open() {
+					proposal.ayes = proposal.ayes.saturating_mul(scale);
+					proposal.nays = proposal.nays.saturating_mul(scale);
+					proposal.abstain = proposal.abstain.saturating_mul(scale);
+				} else if !crate::ProposalRound::<T>::contains_key(proposal_id) {
+					(proposal.ayes, proposal.nays, proposal.abstain) = Default::default();
+					recounted.insert(proposal_id);
+				}
+				Some(proposal)
+			});
+
+			let voters = VotingHistory::<T>::iter_keys().collect::<Vec<_>>();
+			for who in voters {
+				reads += 1;
+				let Some(voting_history) = VotingHistory::<T>::get(&who) else { continue };
+				let voting_history = migrate_voting_history(voting_history);
+				for user_vote in voting_history.iter() {
+					if !recounted.contains(&user_vote.proposal_id) {
+						continue
+					}
+					let (ayes, nays, abstain) =
+						Pallet::<T>::split_votes(user_vote.kind, user_vote.tallied_votes());
+					let delegated = Pallet::<T>::delegated_votes(&who, &user_vote.asset);
+					let (delegated_ayes, delegated_nays, delegated_abstain) =
+						Pallet::<T>::split_delegated_votes(user_vote.kind, delegated);
+					reads += 2;
+					writes += 1;
+					crate::ProposalPool::<T>::mutate(user_vote.proposal_id, |proposal| {
+						let Some(proposal) = proposal else { return };
+						proposal.ayes =
+							proposal.ayes.saturating_add(ayes).saturating_add(delegated_ayes);
+						proposal.nays =
+							proposal.nays.saturating_add(nays).saturating_add(delegated_nays);
+						proposal.abstain = proposal
+							.abstain
+							.saturating_add(abstain)
+							.saturating_add(delegated_abstain);
+					});
+				}
+				crate::VotingHistory::<T>::insert(&who, voting_history);
+				writes += 1;
+			}
+
+			StorageVersion::new(11).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), frame_support::sp_runtime::TryRuntimeError> {
+			ensure!(Pallet::<T>::on_chain_storage_version() == 11, "storage version not updated");
+			let count = u32::decode(&mut &state[..]).map_err(|_| "invalid pre upgrade state")?;
+			let votes: u32 = crate::VotingHistory::<T>::iter_values()
+				.map(|voting_history| voting_history.len() as u32)
+				.sum();
+			ensure!(votes == count, "votes lost in the migration");
+			Ok(())
+		}
+	}
+}
+
+pub mod v12 {
+	use super::*;
+
+	/// Requests the preimages of every open proposal.
+	///
+	/// Up to storage version 11 proposals only fetched their description and call when they were
+	/// made, so the preimages could be removed while they were open. Proposals now request them
+	/// until they are closed, and release them once they are.
+	pub struct MigrateToV12<T>(sp_std::marker::PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV12<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 11 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut reads = 1u64;
+			let mut writes = 1u64;
+			for (proposal_id, proposal) in crate::ProposalPool::<T>::iter() {
+				reads += 2;
+				if !proposal.status.is_open() {
+					continue
+				}
+				for hash in Pallet::<T>::proposal_preimages(proposal_id, &proposal) {
+					T::Preimages::request(&hash);
+					writes += 1;
+				}
+			}
+
+			StorageVersion::new(12).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_: Vec<u8>) -> Result<(), frame_support::sp_runtime::TryRuntimeError> {
+			ensure!(Pallet::<T>::on_chain_storage_version() == 12, "storage version not updated");
+			for (proposal_id, proposal) in crate::ProposalPool::<T>::iter() {
+				if proposal.status.is_open() {
+					ensure!(
+						Pallet::<T>::proposal_preimages(proposal_id, &proposal)
+							.iter()
+							.all(T::Preimages::is_requested),
+						"preimage of an open proposal not requested"
+					);
+				}
+			}
+			Ok(())
+		}
+	}
+}
+
+pub mod v13 {
+	use super::*;
+
+	/// Records the amount held for every vote with the native token in `VoteHolds`.
+	///
+	/// Up to storage version 12 a single hold summed the costs of the votes of an account in
+	/// `LockMode::CumulativeHold`, without keeping the amount held for each proposal. The votes
+	/// of the accounts with tokens held for votes are recorded with their cost, which is what the
+	/// hold summed.
+	pub struct MigrateToV13<T>(sp_std::marker::PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV13<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 12 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let hold_reason: T::RuntimeHoldReason = HoldReason::Vote.into();
+			let mut reads = 1u64;
+			let mut writes = 1u64;
+			for (who, voting_history) in crate::VotingHistory::<T>::iter() {
+				reads += 2;
+				if T::NativeBalance::balance_on_hold(&hold_reason, &who).is_zero() {
+					continue
+				}
+				for user_vote in voting_history.iter().filter(|user_vote| user_vote.asset.is_none())
+				{
+					VoteHolds::<T>::insert(&who, user_vote.proposal_id, user_vote.amount);
+					writes += 1;
+				}
+			}
+
+			StorageVersion::new(13).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_: Vec<u8>) -> Result<(), frame_support::sp_runtime::TryRuntimeError> {
+			ensure!(Pallet::<T>::on_chain_storage_version() == 13, "storage version not updated");
+			let hold_reason: T::RuntimeHoldReason = HoldReason::Vote.into();
+			for (who, _) in crate::VotingHistory::<T>::iter() {
+				let recorded = VoteHolds::<T>::iter_prefix_values(&who)
+					.fold(BalanceOf::<T>::zero(), |total, held| total.saturating_add(held));
+				ensure!(
+					T::NativeBalance::balance_on_hold(&hold_reason, &who) == recorded,
+					"held tokens not recorded"
+				);
+			}
+			Ok(())
+		}
+	}
+}
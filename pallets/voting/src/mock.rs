@@ -1,9 +1,19 @@
 use crate as pallet_voting;
-use frame_support::traits::{ConstU128, ConstU16, ConstU32, ConstU64};
+use crate::{
+	lock_mode::LockMode,
+	tracks::{TrackId, TrackInfo, TracksInfo},
+	vote_threshold::VoteThreshold,
+};
+use frame_support::{
+	parameter_types,
+	traits::{AsEnsureOriginWithArg, ConstU128, ConstU16, ConstU32, ConstU64, EqualPrivilegeOnly},
+	weights::Weight,
+};
+use frame_system::{EnsureRoot, EnsureSigned};
 use sp_core::H256;
 use sp_runtime::{
 	traits::{BlakeTwo256, IdentityLookup},
-	BuildStorage,
+	BuildStorage, Perbill,
 };
 
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -15,6 +25,9 @@ frame_support::construct_runtime!(
 	{
 		System: frame_system,
 		Balances: pallet_balances,
+		Assets: pallet_assets,
+		Preimage: pallet_preimage,
+		Scheduler: pallet_scheduler,
 		Voting: pallet_voting,
 	}
 );
@@ -55,20 +68,182 @@ impl pallet_balances::Config for Test {
 	type MaxLocks = ConstU32<10>;
 	type MaxReserves = ();
 	type ReserveIdentifier = [u8; 8];
-	type RuntimeHoldReason = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
 	type FreezeIdentifier = RuntimeFreezeReason;
 	type MaxHolds = ConstU32<10>;
 	type MaxFreezes = ConstU32<10>;
 }
 
+/// The asset voted with on the community track.
+pub const COMMUNITY_ASSET: u32 = 7;
+
+impl pallet_assets::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = Balance;
+	type RemoveItemsLimit = ConstU32<1000>;
+	type AssetId = u32;
+	type AssetIdParameter = u32;
+	type Currency = Balances;
+	type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<u64>>;
+	type ForceOrigin = EnsureRoot<u64>;
+	type AssetDeposit = ConstU128<0>;
+	type AssetAccountDeposit = ConstU128<0>;
+	type MetadataDepositBase = ConstU128<0>;
+	type MetadataDepositPerByte = ConstU128<0>;
+	type ApprovalDeposit = ConstU128<0>;
+	type StringLimit = ConstU32<50>;
+	type Freezer = Voting;
+	type Extra = ();
+	type CallbackHandle = ();
+	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+}
+
+impl pallet_preimage::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+	type Currency = Balances;
+	type ManagerOrigin = EnsureRoot<u64>;
+	type BaseDeposit = ConstU128<0>;
+	type ByteDeposit = ConstU128<0>;
+}
+
+parameter_types! {
+	pub MaximumSchedulerWeight: Weight = Weight::from_parts(1_000_000_000_000, u64::MAX);
+	pub EnactmentOrigin: OriginCaller = frame_system::RawOrigin::Root.into();
+}
+
+impl pallet_scheduler::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeOrigin = RuntimeOrigin;
+	type PalletsOrigin = OriginCaller;
+	type RuntimeCall = RuntimeCall;
+	type MaximumWeight = MaximumSchedulerWeight;
+	type ScheduleOrigin = EnsureRoot<u64>;
+	type MaxScheduledPerBlock = ConstU32<100>;
+	type WeightInfo = ();
+	type OriginPrivilegeCmp = EqualPrivilegeOnly;
+	type Preimages = Preimage;
+}
+
+parameter_types! {
+	pub static VerifiedAccounts: Vec<u64> = vec![];
+	pub static ProposalDeposit: u128 = 0;
+	pub static MinVoters: u32 = 1;
+	pub static MinApproval: Perbill = Perbill::from_percent(50);
+	pub static Threshold: VoteThreshold = VoteThreshold::SimpleMajority;
+	pub static VoteLockMode: LockMode = LockMode::OverlappingFreeze;
+	pub static VotePrecision: u32 = 1;
+	pub static NoTracks: bool = false;
+}
+
+/// Identity provider that accepts the accounts in `VerifiedAccounts`.
+pub struct TestIdentity;
+
+impl pallet_voting::identity::IdentityProvider<u64> for TestIdentity {
+	fn has_identity(who: &u64) -> bool {
+		VerifiedAccounts::get().contains(who)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn ensure_verified(who: &u64) {
+		VerifiedAccounts::mutate(|accounts| accounts.push(*who));
+	}
+}
+
+/// A general track, a track whose voting period starts after a delay and a track voting with
+/// `COMMUNITY_ASSET`, all with the deposit in `ProposalDeposit`, the quorum in `MinVoters` and the
+/// approval thresholds in `MinApproval` and `Threshold`. Proposals of the community track can
+/// only carry remarks. There are no tracks at all when `NoTracks` is set.
+pub struct TestTracks;
+
+impl TracksInfo<Balance, u64, u32, RuntimeCall> for TestTracks {
+	fn tracks() -> Vec<(TrackId, TrackInfo<Balance, u64, u32>)> {
+		if NoTracks::get() {
+			return vec![]
+		}
+		vec![
+			(
+				0,
+				TrackInfo {
+					name: "general",
+					start_delay: 0,
+					duration: 9,
+					min_participation: 2,
+					min_voters: MinVoters::get(),
+					min_approval: MinApproval::get(),
+					threshold: Threshold::get(),
+					deposit: ProposalDeposit::get(),
+					asset: None,
+				},
+			),
+			(
+				1,
+				TrackInfo {
+					name: "delayed",
+					start_delay: 5,
+					duration: 3,
+					min_participation: 2,
+					min_voters: MinVoters::get(),
+					min_approval: MinApproval::get(),
+					threshold: Threshold::get(),
+					deposit: ProposalDeposit::get(),
+					asset: None,
+				},
+			),
+			(
+				2,
+				TrackInfo {
+					name: "community",
+					start_delay: 0,
+					duration: 9,
+					min_participation: 2,
+					min_voters: MinVoters::get(),
+					min_approval: MinApproval::get(),
+					threshold: Threshold::get(),
+					deposit: ProposalDeposit::get(),
+					asset: Some(COMMUNITY_ASSET),
+				},
+			),
+		]
+	}
+
+	fn authorize_call(id: TrackId, call: &RuntimeCall) -> bool {
+		id != 2 || matches!(call, RuntimeCall::System(frame_system::Call::remark { .. }))
+	}
+}
+
 impl pallet_voting::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type NativeBalance = Balances;
+	type Assets = Assets;
 	type BlockNumberToBalance = sp_runtime::traits::ConvertInto;
 	type RuntimeFreezeReason = RuntimeFreezeReason;
+	type RuntimeHoldReason = RuntimeHoldReason;
 	type MaxVotes = ConstU32<100>;
-	type ProposalDuration = ConstU64<9>;
+	type Tracks = TestTracks;
 	type ProposalId = u64;
+	type MaxTitleLength = ConstU32<64>;
+	type MaxDescriptionLength = ConstU32<256>;
+	type MaxProposalsEndingPerBlock = ConstU32<128>;
+	type MaxAutoClosePerBlock = ConstU32<2>;
+	type Preimages = Preimage;
+	type Scheduler = Scheduler;
+	type EnactmentOrigin = EnactmentOrigin;
+	type MaxRegistrationBatch = ConstU32<10>;
+	type RegistrarOrigin = EnsureRoot<u64>;
+	type RoundOrigin = EnsureRoot<u64>;
+	type MaxProposalsPerRound = ConstU32<4>;
+	type Slash = ();
+	type CancelOrigin = EnsureRoot<u64>;
+	type WithdrawalPeriod = ConstU64<2>;
+	type IdentityProvider = TestIdentity;
+	type EnactmentPeriod = ConstU64<2>;
+	type VoteLockingPeriod = ConstU64<10>;
+	type LockMode = VoteLockMode;
+	type VotePrecision = VotePrecision;
+	type WeightInfo = ();
 }
 
 // Build genesis storage according to the mock runtime.
@@ -1,8 +1,14 @@
-use crate::{mock::*, Error, Event};
+use crate::{
+	mock::*, Error, Event, ProposalDescription, ProposalMetadata, ProposalStatus, RegistrationKind,
+	VoteKind,
+};
 use frame_support::{
 	assert_noop, assert_ok,
 	pallet_prelude::DispatchError,
-	traits::fungible::{InspectFreeze, Mutate},
+	traits::{
+		fungible::{InspectFreeze, Mutate},
+		Hooks, StorePreimage,
+	},
 };
 
 type NativeBalance = <Test as crate::Config>::NativeBalance;
@@ -22,7 +28,10 @@ mod register {
 			// Check that the event was generated.
 			System::assert_last_event(Event::VoterRegistered { voter: alice }.into());
 			// Check that the account was written to storage.
-			assert!(<crate::pallet::RegisteredAccounts<Test>>::get(0).unwrap());
+			assert_eq!(
+				<crate::pallet::RegisteredAccounts<Test>>::get(0),
+				Some(RegistrationKind::Manual)
+			);
 		});
 	}
 
@@ -56,7 +65,10 @@ mod register {
 			// Check that the event was generated.
 			System::assert_last_event(Event::VoterRegistered { voter: alice }.into());
 			// Check that the account was written to storage.
-			assert!(<crate::pallet::RegisteredAccounts<Test>>::get(0).unwrap());
+			assert_eq!(
+				<crate::pallet::RegisteredAccounts<Test>>::get(0),
+				Some(RegistrationKind::Manual)
+			);
 			// Try to register alice again. Should not work.
 			assert_noop!(
 				Voting::register_voters(RuntimeOrigin::root(), alice),
@@ -66,10 +78,485 @@ mod register {
 			assert!(<crate::pallet::RegisteredAccounts<Test>>::get(1).is_none());
 		});
 	}
+
+	#[test]
+	fn registrar_registers_within_quota() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let registrar = 10;
+			assert_noop!(
+				Voting::set_registrar(RuntimeOrigin::signed(registrar), registrar, Some(2)),
+				DispatchError::BadOrigin
+			);
+			assert_ok!(Voting::set_registrar(RuntimeOrigin::root(), registrar, Some(2)));
+			System::assert_last_event(Event::RegistrarSet { registrar, quota: Some(2) }.into());
+
+			assert_ok!(Voting::register_voters(RuntimeOrigin::signed(registrar), 0));
+			System::assert_last_event(Event::VoterRegistered { voter: 0 }.into());
+			assert_ok!(Voting::register_voters(RuntimeOrigin::signed(registrar), 1));
+			assert_eq!(<crate::pallet::Registrars<Test>>::get(registrar), Some(0));
+
+			assert_noop!(
+				Voting::register_voters(RuntimeOrigin::signed(registrar), 2),
+				Error::<Test>::RegistrarQuotaExhausted
+			);
+			// Registering an account twice doesn't use the quota.
+			assert_ok!(Voting::set_registrar(RuntimeOrigin::root(), registrar, Some(1)));
+			assert_noop!(
+				Voting::register_voters(RuntimeOrigin::signed(registrar), 0),
+				Error::<Test>::VoterAlreadyRegistered
+			);
+			assert_eq!(<crate::pallet::Registrars<Test>>::get(registrar), Some(1));
+		});
+	}
+
+	#[test]
+	fn removed_registrar_cannot_register() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let registrar = 10;
+			assert_ok!(Voting::set_registrar(RuntimeOrigin::root(), registrar, Some(5)));
+			assert_ok!(Voting::set_registrar(RuntimeOrigin::root(), registrar, None));
+			System::assert_last_event(Event::RegistrarSet { registrar, quota: None }.into());
+
+			assert_noop!(
+				Voting::register_voters(RuntimeOrigin::signed(registrar), 0),
+				DispatchError::BadOrigin
+			);
+		});
+	}
+
+	#[test]
+	fn batch_registration_reports_skipped_accounts() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Voting::register_voters(RuntimeOrigin::root(), 0));
+
+			assert_ok!(Voting::register_voters_batch(RuntimeOrigin::root(), vec![0, 1, 2, 1]));
+			System::assert_last_event(
+				Event::VotersBatchRegistered { registered: 2, skipped: vec![0, 1] }.into(),
+			);
+			assert_eq!(
+				<crate::pallet::RegisteredAccounts<Test>>::get(2),
+				Some(RegistrationKind::Manual)
+			);
+
+			assert_noop!(
+				Voting::register_voters_batch(RuntimeOrigin::root(), (10..21).collect()),
+				Error::<Test>::TooManyVotersInBatch
+			);
+		});
+	}
+
+	#[test]
+	fn batch_registration_uses_registrar_quota() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let registrar = 10;
+			assert_noop!(
+				Voting::register_voters_batch(RuntimeOrigin::signed(registrar), vec![0]),
+				DispatchError::BadOrigin
+			);
+
+			assert_ok!(Voting::set_registrar(RuntimeOrigin::root(), registrar, Some(2)));
+			assert_ok!(Voting::register_voters_batch(
+				RuntimeOrigin::signed(registrar),
+				vec![0, 1, 2]
+			));
+			System::assert_last_event(
+				Event::VotersBatchRegistered { registered: 2, skipped: vec![2] }.into(),
+			);
+			assert_eq!(<crate::pallet::Registrars<Test>>::get(registrar), Some(0));
+			assert!(<crate::pallet::RegisteredAccounts<Test>>::get(2).is_none());
+		});
+	}
+
+	#[test]
+	fn genesis_config_registers_voters_and_proposals() {
+		use sp_runtime::BuildStorage;
+
+		let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+		crate::GenesisConfig::<Test> { voters: vec![0, 1], proposals: vec![vec![1, 2, 3]] }
+			.assimilate_storage(&mut storage)
+			.unwrap();
+		sp_io::TestExternalities::new(storage).execute_with(|| {
+			assert_eq!(
+				<crate::pallet::RegisteredAccounts<Test>>::get(1),
+				Some(RegistrationKind::Manual)
+			);
+			assert!(<crate::pallet::ProposalPool<Test>>::get(0).is_some());
+			assert_eq!(<crate::pallet::ProposalIndex<Test>>::get(), 1);
+			assert_eq!(<crate::pallet::ProposalsEndingAt<Test>>::get(9), vec![0]);
+		});
+	}
+
+	#[test]
+	fn legacy_registration_is_manual() {
+		new_test_ext().execute_with(|| {
+			// Earlier versions stored `true` for every registered account.
+			frame_support::storage::unhashed::put(
+				&<crate::pallet::RegisteredAccounts<Test>>::hashed_key_for(0),
+				&true,
+			);
+			assert_eq!(
+				<crate::pallet::RegisteredAccounts<Test>>::get(0),
+				Some(RegistrationKind::Manual)
+			);
+		});
+	}
+
+	#[test]
+	fn register_self_with_identity() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let alice = 0;
+			VerifiedAccounts::set(vec![alice]);
+
+			assert_ok!(Voting::register_self(RuntimeOrigin::signed(alice)));
+			System::assert_last_event(Event::VoterRegistered { voter: alice }.into());
+			assert_eq!(
+				<crate::pallet::RegisteredAccounts<Test>>::get(alice),
+				Some(RegistrationKind::Identity)
+			);
+
+			assert_noop!(
+				Voting::register_self(RuntimeOrigin::signed(alice)),
+				Error::<Test>::VoterAlreadyRegistered
+			);
+		});
+	}
+
+	#[test]
+	fn register_self_without_identity_fails() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let alice = 0;
+
+			assert_noop!(
+				Voting::register_self(RuntimeOrigin::signed(alice)),
+				Error::<Test>::InsufficientIdentity
+			);
+			assert!(<crate::pallet::RegisteredAccounts<Test>>::get(alice).is_none());
+		});
+	}
+
+	#[test]
+	fn revoked_identity_cannot_vote() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let alice = 0;
+			assert_ok!(NativeBalance::mint_into(&alice, 100));
+			VerifiedAccounts::set(vec![alice]);
+			assert_ok!(Voting::register_self(RuntimeOrigin::signed(alice)));
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 1, VoteKind::Aye, 0, None));
+
+			// The judgement is revoked.
+			VerifiedAccounts::set(vec![]);
+			assert_noop!(
+				Voting::vote(RuntimeOrigin::signed(alice), 2, VoteKind::Aye, 0, None),
+				Error::<Test>::InsufficientIdentity
+			);
+			assert_noop!(
+				Voting::make_proposal(
+					RuntimeOrigin::signed(alice),
+					0,
+					test_utils::metadata(&[0, 1, 2, 3, 4]),
+					None
+				),
+				Error::<Test>::InsufficientIdentity
+			);
+
+			// Tokens frozen for earlier votes can still be claimed.
+			System::set_block_number(11);
+			assert_ok!(Voting::end_vote(RuntimeOrigin::signed(alice), 0));
+			assert_ok!(Voting::claim_frozen_tokens(RuntimeOrigin::signed(alice), 0));
+		});
+	}
+}
+
+mod registrar {
+	use super::*;
+
+	#[test]
+	fn deregister_removes_open_votes() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(bob),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 4, VoteKind::Aye, 0, None));
+			System::set_block_number(2);
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(bob),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 3, VoteKind::Nay, 1, None));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 1, VoteKind::Aye, 1, None));
+
+			// Proposal 0 ends, proposal 1 is still open.
+			System::set_block_number(10);
+			assert_ok!(Voting::end_vote(RuntimeOrigin::signed(bob), 0));
+
+			assert_noop!(
+				Voting::deregister_voter(RuntimeOrigin::signed(bob), alice),
+				DispatchError::BadOrigin
+			);
+			assert_ok!(Voting::deregister_voter(RuntimeOrigin::root(), alice));
+			System::assert_last_event(
+				Event::VoterDeregistered { voter: alice, votes_removed: 1 }.into(),
+			);
+			assert!(<crate::pallet::RegisteredAccounts<Test>>::get(alice).is_none());
+
+			// The vote on the open proposal is gone from the tally.
+			let proposal = <crate::pallet::ProposalPool<Test>>::get(1).unwrap();
+			assert_eq!((proposal.ayes, proposal.nays), (1, 0));
+			// Only the vote on the ended proposal stays frozen and can be claimed.
+			assert_eq!(
+				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
+				16
+			);
+			assert_ok!(Voting::claim_frozen_tokens(RuntimeOrigin::signed(alice), 0));
+			assert_eq!(
+				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
+				0
+			);
+
+			assert_noop!(
+				Voting::vote(RuntimeOrigin::signed(alice), 1, VoteKind::Aye, 1, None),
+				Error::<Test>::NotRegistered
+			);
+			assert_noop!(
+				Voting::deregister_voter(RuntimeOrigin::root(), alice),
+				Error::<Test>::NotRegistered
+			);
+		});
+	}
+
+	#[test]
+	fn suspended_voter_cannot_vote_until_block() {
+		new_test_ext().execute_with(|| {
+			let (alice, _) = test_utils::setup();
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 2, VoteKind::Aye, 0, None));
+
+			assert_ok!(Voting::suspend_voter(RuntimeOrigin::root(), alice, 5));
+			System::assert_last_event(Event::VoterSuspended { voter: alice, until: 5 }.into());
+			assert_noop!(
+				Voting::vote(RuntimeOrigin::signed(alice), 3, VoteKind::Aye, 0, None),
+				Error::<Test>::VoterSuspended
+			);
+			assert_noop!(
+				Voting::make_proposal(
+					RuntimeOrigin::signed(alice),
+					0,
+					test_utils::metadata(&[0, 1, 2, 3, 4]),
+					None
+				),
+				Error::<Test>::VoterSuspended
+			);
+			// The votes cast before the suspension are kept.
+			assert_eq!(<crate::pallet::ProposalPool<Test>>::get(0).unwrap().ayes, 2);
+
+			System::set_block_number(5);
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 3, VoteKind::Aye, 0, None));
+		});
+	}
+
+	#[test]
+	fn suspension_can_be_lifted() {
+		new_test_ext().execute_with(|| {
+			let (alice, _) = test_utils::setup();
+			assert_ok!(Voting::suspend_voter(RuntimeOrigin::root(), alice, 100));
+			assert_ok!(Voting::suspend_voter(RuntimeOrigin::root(), alice, 1));
+			System::assert_last_event(Event::VoterReinstated { voter: alice }.into());
+			assert!(<crate::pallet::SuspendedUntil<Test>>::get(alice).is_none());
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
+		});
+	}
+
+	#[test]
+	fn expired_registration_cannot_vote() {
+		new_test_ext().execute_with(|| {
+			let (alice, _) = test_utils::setup();
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
+
+			assert_ok!(Voting::set_registration_expiry(RuntimeOrigin::root(), alice, Some(3)));
+			System::assert_last_event(
+				Event::RegistrationExpirySet { voter: alice, expiry: Some(3) }.into(),
+			);
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 1, VoteKind::Aye, 0, None));
+
+			System::set_block_number(3);
+			assert_noop!(
+				Voting::vote(RuntimeOrigin::signed(alice), 2, VoteKind::Aye, 0, None),
+				Error::<Test>::RegistrationExpired
+			);
+
+			assert_ok!(Voting::set_registration_expiry(RuntimeOrigin::root(), alice, None));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 2, VoteKind::Aye, 0, None));
+		});
+	}
+
+	#[test]
+	fn unregistered_account_cannot_be_suspended() {
+		new_test_ext().execute_with(|| {
+			test_utils::setup();
+			assert_noop!(
+				Voting::suspend_voter(RuntimeOrigin::root(), 5, 10),
+				Error::<Test>::NotRegistered
+			);
+			assert_noop!(
+				Voting::set_registration_expiry(RuntimeOrigin::root(), 5, Some(10)),
+				Error::<Test>::NotRegistered
+			);
+		});
+	}
 }
 
 mod proposal {
 	use super::*;
+	use codec::Encode;
+	use frame_support::traits::{Bounded, QueryPreimage};
+
+	#[test]
+	fn proposal_metadata_is_stored() {
+		new_test_ext().execute_with(|| {
+			let (alice, _) = test_utils::setup();
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3]),
+				None
+			));
+			assert_eq!(
+				<crate::pallet::ProposalMetadataOf<Test>>::get(0),
+				Some(test_utils::metadata(&[0, 1, 2, 3]))
+			);
+			assert_eq!(
+				<crate::pallet::ProposalPool<Test>>::get(0).unwrap().description,
+				test_utils::description_hash(&[0, 1, 2, 3])
+			);
+		});
+	}
+
+	#[test]
+	fn proposal_description_can_be_a_preimage() {
+		new_test_ext().execute_with(|| {
+			let (alice, _) = test_utils::setup();
+			// The description is longer than `MaxDescriptionLength`.
+			let text = vec![7u8; 1000];
+			assert_ok!(Preimage::note_preimage(RuntimeOrigin::signed(alice), text.clone()));
+			let hash = sp_core::blake2_256(&text).into();
+			let metadata = |len| ProposalMetadata {
+				title: b"title".to_vec().try_into().unwrap(),
+				description: ProposalDescription::Preimage { hash, len },
+			};
+
+			// The length has to match the noted preimage.
+			assert_noop!(
+				Voting::make_proposal(RuntimeOrigin::signed(alice), 0, metadata(999), None),
+				Error::<Test>::DescriptionUnavailable
+			);
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				metadata(1000),
+				None
+			));
+			System::assert_last_event(
+				Event::ProposalCreated {
+					proposal_id: 0,
+					description_hash: test_utils::description_hash(&text),
+				}
+				.into(),
+			);
+			assert_eq!(<crate::pallet::ProposalMetadataOf<Test>>::get(0), Some(metadata(1000)));
+		});
+	}
+
+	#[test]
+	fn preimages_are_requested_while_the_proposal_is_open() {
+		new_test_ext().execute_with(|| {
+			let (alice, _) = test_utils::setup();
+			let text = vec![7u8; 1000];
+			let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![1u8; 200] });
+			for preimage in [text.clone(), call.encode()] {
+				assert_ok!(Preimage::note_preimage(RuntimeOrigin::signed(alice), preimage));
+			}
+			let text_hash = sp_core::blake2_256(&text).into();
+			let call_hash = sp_core::blake2_256(&call.encode()).into();
+			let requested = |hash| <Preimage as QueryPreimage>::is_requested(&hash);
+
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				ProposalMetadata {
+					title: b"title".to_vec().try_into().unwrap(),
+					description: ProposalDescription::Preimage { hash: text_hash, len: 1000 },
+				},
+				None
+			));
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				Some(Bounded::Lookup { hash: call_hash, len: call.encoded_size() as u32 })
+			));
+			assert!(requested(text_hash));
+			assert!(requested(call_hash));
+
+			// Closing or cancelling the proposals releases their preimages.
+			assert_ok!(Voting::cancel_proposal(RuntimeOrigin::root(), 1));
+			assert!(!requested(call_hash));
+			System::set_block_number(10);
+			assert_ok!(Voting::end_vote(RuntimeOrigin::signed(alice), 0));
+			assert!(!requested(text_hash));
+		});
+	}
+
+	#[test]
+	fn add_proposal_with_unnoted_preimage_fails() {
+		new_test_ext().execute_with(|| {
+			let (alice, _) = test_utils::setup();
+			let metadata = ProposalMetadata {
+				title: b"title".to_vec().try_into().unwrap(),
+				description: ProposalDescription::Preimage {
+					hash: sp_core::blake2_256(&[0, 1, 2, 3]).into(),
+					len: 4,
+				},
+			};
+			assert_noop!(
+				Voting::make_proposal(RuntimeOrigin::signed(alice), 0, metadata, None),
+				Error::<Test>::DescriptionUnavailable
+			);
+		});
+	}
 
 	#[test]
 	fn add_proposal_from_unregistered_user_fails() {
@@ -82,7 +569,12 @@ mod proposal {
 			assert_ok!(NativeBalance::mint_into(&alice, 100));
 			// Alice is not registered so she can't make a proposal.
 			assert_noop!(
-				Voting::make_proposal(RuntimeOrigin::signed(alice), vec![0, 1, 2, 3]),
+				Voting::make_proposal(
+					RuntimeOrigin::signed(alice),
+					0,
+					test_utils::metadata(&[0, 1, 2, 3]),
+					None
+				),
 				Error::<Test>::NotRegistered
 			);
 		});
@@ -95,17 +587,39 @@ mod proposal {
 			// Check that there is no proposal
 			assert!(<crate::pallet::ProposalPool<Test>>::get(0).is_none());
 			// Alice makes a proposal.
-			assert_ok!(Voting::make_proposal(RuntimeOrigin::signed(alice), vec![0, 1, 2, 3]));
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3]),
+				None
+			));
 			// Assert that the correct event was deposited
-			System::assert_last_event(Event::ProposalCreated { proposal_id: 0 }.into());
+			System::assert_last_event(
+				Event::ProposalCreated {
+					proposal_id: 0,
+					description_hash: test_utils::description_hash(&[0, 1, 2, 3]),
+				}
+				.into(),
+			);
 			// Check that the proposal pool has been updated
 			assert!(<crate::pallet::ProposalPool<Test>>::get(0).is_some());
 			// Advance to the next block.
 			System::set_block_number(2);
 			// Bob makes a proposal.
-			assert_ok!(Voting::make_proposal(RuntimeOrigin::signed(bob), vec![0, 1, 2, 3, 4]));
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(bob),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
 			// Assert that the correct event was deposited
-			System::assert_last_event(Event::ProposalCreated { proposal_id: 1 }.into());
+			System::assert_last_event(
+				Event::ProposalCreated {
+					proposal_id: 1,
+					description_hash: test_utils::description_hash(&[0, 1, 2, 3, 4]),
+				}
+				.into(),
+			);
 			// Check that the proposal pool has been updated
 			assert!(<crate::pallet::ProposalPool<Test>>::get(1).is_some());
 		});
@@ -122,7 +636,7 @@ mod vote {
 			// No matter if the proposal exists, Alice is unregistered so that is the error she will
 			// see.
 			assert_noop!(
-				Voting::vote(RuntimeOrigin::signed(alice), 1, true, 0),
+				Voting::vote(RuntimeOrigin::signed(alice), 1, VoteKind::Aye, 0, None),
 				Error::<Test>::NotRegistered
 			);
 		});
@@ -134,7 +648,7 @@ mod vote {
 			let (alice, _) = test_utils::setup();
 			// Trying to vote for a proposal that doesnt exist.
 			assert_noop!(
-				Voting::vote(RuntimeOrigin::signed(alice), 1, true, 0),
+				Voting::vote(RuntimeOrigin::signed(alice), 1, VoteKind::Aye, 0, None),
 				Error::<Test>::ProposalDoesNotExist
 			);
 		});
@@ -144,13 +658,18 @@ mod vote {
 	fn add_vote_after_proposal_ends_fails() {
 		new_test_ext().execute_with(|| {
 			let (alice, _) = test_utils::setup();
-			assert_ok!(Voting::make_proposal(RuntimeOrigin::signed(alice), vec![0, 1, 2, 3, 4]));
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
 			// Make a proposal and finish it.
 			System::set_block_number(100000);
 			assert_ok!(Voting::end_vote(RuntimeOrigin::signed(alice), 0));
 			// Trying to vote for a proposal that is finished.
 			assert_noop!(
-				Voting::vote(RuntimeOrigin::signed(alice), 1, true, 0),
+				Voting::vote(RuntimeOrigin::signed(alice), 1, VoteKind::Aye, 0, None),
 				Error::<Test>::VoteAlreadyEnded
 			);
 		});
@@ -161,16 +680,21 @@ mod vote {
 		new_test_ext().execute_with(|| {
 			let (alice, bob) = test_utils::setup();
 
-			assert_ok!(Voting::make_proposal(RuntimeOrigin::signed(alice), vec![0, 1, 2, 3, 4]));
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
 			// Cast 1 aye.
-			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 1, true, 0));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 1, VoteKind::Aye, 0, None));
 
 			// Check storage was successfully set.
 			let proposal = <crate::pallet::ProposalPool<Test>>::get(0).unwrap();
 			assert_eq!(proposal.ayes, 1);
 
 			// Cast 2 nays
-			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 2, true, 0));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 2, VoteKind::Aye, 0, None));
 			// Check storage was successfully set.
 			let proposal = <crate::pallet::ProposalPool<Test>>::get(0).unwrap();
 			assert_eq!(proposal.ayes, 3);
@@ -182,9 +706,14 @@ mod vote {
 		new_test_ext().execute_with(|| {
 			let (alice, bob) = test_utils::setup();
 
-			assert_ok!(Voting::make_proposal(RuntimeOrigin::signed(alice), vec![0, 1, 2, 3, 4]));
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
 			// Cast 1 nay.
-			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 1, false, 0));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 1, VoteKind::Nay, 0, None));
 			// Check the correct event is emitted.
 			System::assert_last_event(Event::VoteAddedTo { proposal_id: 0, votes: 1 }.into());
 			// Check storage was successfully set.
@@ -192,7 +721,7 @@ mod vote {
 			assert_eq!(proposal.nays, 1);
 
 			// Cast 2 nays.
-			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 2, false, 0));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 2, VoteKind::Nay, 0, None));
 			// Check the correct event is emitted.
 			System::assert_last_event(Event::VoteAddedTo { proposal_id: 0, votes: 2 }.into());
 			// Check storage was successfully set.
@@ -208,24 +737,49 @@ mod vote {
 
 			// Alice makes a proposal.
 			assert_eq!(
-				Voting::make_proposal(RuntimeOrigin::signed(alice), vec![0, 1, 2, 3]),
+				Voting::make_proposal(
+					RuntimeOrigin::signed(alice),
+					0,
+					test_utils::metadata(&[0, 1, 2, 3]),
+					None
+				),
 				Ok(())
 			);
 			// Assert that the correct event was deposited.
-			System::assert_last_event(Event::ProposalCreated { proposal_id: 0 }.into());
+			System::assert_last_event(
+				Event::ProposalCreated {
+					proposal_id: 0,
+					description_hash: test_utils::description_hash(&[0, 1, 2, 3]),
+				}
+				.into(),
+			);
 
 			// Advance to the next block.
 			System::set_block_number(2);
 			// Bob makes a proposal.
 			assert_eq!(
-				Voting::make_proposal(RuntimeOrigin::signed(bob), vec![0, 1, 2, 3, 4]),
+				Voting::make_proposal(
+					RuntimeOrigin::signed(bob),
+					0,
+					test_utils::metadata(&[0, 1, 2, 3, 4]),
+					None
+				),
 				Ok(())
 			);
 			// Assert that the correct event was deposited.
-			System::assert_last_event(Event::ProposalCreated { proposal_id: 1 }.into());
+			System::assert_last_event(
+				Event::ProposalCreated {
+					proposal_id: 1,
+					description_hash: test_utils::description_hash(&[0, 1, 2, 3, 4]),
+				}
+				.into(),
+			);
 
 			// Vote and check that the history is kept.
-			assert_eq!(Voting::vote(RuntimeOrigin::signed(alice), 5, true, 0), Ok(()));
+			assert_eq!(
+				Voting::vote(RuntimeOrigin::signed(alice), 5, VoteKind::Aye, 0, None),
+				Ok(())
+			);
 			assert_eq!(<crate::pallet::VotingHistory<Test>>::get(alice).unwrap().len(), 1);
 		});
 	}
@@ -236,24 +790,51 @@ mod vote {
 			let (alice, bob) = test_utils::setup();
 			// Alice makes a proposal.
 			assert_eq!(
-				Voting::make_proposal(RuntimeOrigin::signed(alice), vec![0, 1, 2, 3]),
+				Voting::make_proposal(
+					RuntimeOrigin::signed(alice),
+					0,
+					test_utils::metadata(&[0, 1, 2, 3]),
+					None
+				),
 				Ok(())
 			);
 			// Assert that the correct event was deposited.
-			System::assert_last_event(Event::ProposalCreated { proposal_id: 0 }.into());
+			System::assert_last_event(
+				Event::ProposalCreated {
+					proposal_id: 0,
+					description_hash: test_utils::description_hash(&[0, 1, 2, 3]),
+				}
+				.into(),
+			);
 
 			// Advance to the next block.
 			System::set_block_number(2);
 			// Bob makes a proposal.
 			assert_eq!(
-				Voting::make_proposal(RuntimeOrigin::signed(bob), vec![0, 1, 2, 3, 4]),
+				Voting::make_proposal(
+					RuntimeOrigin::signed(bob),
+					0,
+					test_utils::metadata(&[0, 1, 2, 3, 4]),
+					None
+				),
 				Ok(())
 			);
 			// Assert that the correct event was deposited.
-			System::assert_last_event(Event::ProposalCreated { proposal_id: 1 }.into());
+			System::assert_last_event(
+				Event::ProposalCreated {
+					proposal_id: 1,
+					description_hash: test_utils::description_hash(&[0, 1, 2, 3, 4]),
+				}
+				.into(),
+			);
 
 			assert_eq!(
-				Voting::make_proposal(RuntimeOrigin::signed(bob), vec![0, 1, 2, 3, 4]),
+				Voting::make_proposal(
+					RuntimeOrigin::signed(bob),
+					0,
+					test_utils::metadata(&[0, 1, 2, 3, 4]),
+					None
+				),
 				Ok(())
 			);
 
@@ -261,7 +842,10 @@ mod vote {
 				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
 				0
 			);
-			assert_eq!(Voting::vote(RuntimeOrigin::signed(alice), 5, true, 0), Ok(()));
+			assert_eq!(
+				Voting::vote(RuntimeOrigin::signed(alice), 5, VoteKind::Aye, 0, None),
+				Ok(())
+			);
 			// Check voting history is added.
 			assert_eq!(<crate::pallet::VotingHistory<Test>>::get(alice).unwrap().len(), 1);
 			//Check the frozen amount is correct.
@@ -270,7 +854,10 @@ mod vote {
 				25
 			);
 			// Vote again on a different proposal.
-			assert_eq!(Voting::vote(RuntimeOrigin::signed(alice), 6, false, 1), Ok(()));
+			assert_eq!(
+				Voting::vote(RuntimeOrigin::signed(alice), 6, VoteKind::Nay, 1, None),
+				Ok(())
+			);
 			// Check voting history is added.
 			assert_eq!(<crate::pallet::VotingHistory<Test>>::get(alice).unwrap().len(), 2);
 			// Check frozen amount is increased.
@@ -279,7 +866,10 @@ mod vote {
 				36
 			);
 			// Vote on a third proposal.
-			assert_eq!(Voting::vote(RuntimeOrigin::signed(alice), 3, true, 2), Ok(()));
+			assert_eq!(
+				Voting::vote(RuntimeOrigin::signed(alice), 3, VoteKind::Aye, 2, None),
+				Ok(())
+			);
 			// Check voting history is added.
 			assert_eq!(<crate::pallet::VotingHistory<Test>>::get(alice).unwrap().len(), 3);
 		});
@@ -292,18 +882,32 @@ mod vote {
 
 			// Alice makes a proposal.
 			assert_eq!(
-				Voting::make_proposal(RuntimeOrigin::signed(alice), vec![0, 1, 2, 3]),
+				Voting::make_proposal(
+					RuntimeOrigin::signed(alice),
+					0,
+					test_utils::metadata(&[0, 1, 2, 3]),
+					None
+				),
 				Ok(())
 			);
 			// Assert that the correct event was deposited
-			System::assert_last_event(Event::ProposalCreated { proposal_id: 0 }.into());
+			System::assert_last_event(
+				Event::ProposalCreated {
+					proposal_id: 0,
+					description_hash: test_utils::description_hash(&[0, 1, 2, 3]),
+				}
+				.into(),
+			);
 			// Check the frozen balance is zero before a vote.
 			assert_eq!(
 				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
 				0
 			);
 
-			assert_eq!(Voting::vote(RuntimeOrigin::signed(alice), 5, true, 0), Ok(()));
+			assert_eq!(
+				Voting::vote(RuntimeOrigin::signed(alice), 5, VoteKind::Aye, 0, None),
+				Ok(())
+			);
 			// Check voting history is added.
 			assert_eq!(<crate::pallet::VotingHistory<Test>>::get(alice).unwrap().len(), 1);
 			// Check frozen_balance is increased.
@@ -321,24 +925,51 @@ mod vote {
 
 			// Alice makes a proposal.
 			assert_eq!(
-				Voting::make_proposal(RuntimeOrigin::signed(alice), vec![0, 1, 2, 3]),
+				Voting::make_proposal(
+					RuntimeOrigin::signed(alice),
+					0,
+					test_utils::metadata(&[0, 1, 2, 3]),
+					None
+				),
 				Ok(())
 			);
 			// Assert that the correct event was deposited
-			System::assert_last_event(Event::ProposalCreated { proposal_id: 0 }.into());
+			System::assert_last_event(
+				Event::ProposalCreated {
+					proposal_id: 0,
+					description_hash: test_utils::description_hash(&[0, 1, 2, 3]),
+				}
+				.into(),
+			);
 
 			// Advance to the next block.
 			System::set_block_number(2);
 			// Bob makes a proposal.
 			assert_eq!(
-				Voting::make_proposal(RuntimeOrigin::signed(bob), vec![0, 1, 2, 3, 4]),
+				Voting::make_proposal(
+					RuntimeOrigin::signed(bob),
+					0,
+					test_utils::metadata(&[0, 1, 2, 3, 4]),
+					None
+				),
 				Ok(())
 			);
 			// Assert that the correct event was deposited
-			System::assert_last_event(Event::ProposalCreated { proposal_id: 1 }.into());
+			System::assert_last_event(
+				Event::ProposalCreated {
+					proposal_id: 1,
+					description_hash: test_utils::description_hash(&[0, 1, 2, 3, 4]),
+				}
+				.into(),
+			);
 
 			assert_eq!(
-				Voting::make_proposal(RuntimeOrigin::signed(bob), vec![0, 1, 2, 3, 4]),
+				Voting::make_proposal(
+					RuntimeOrigin::signed(bob),
+					0,
+					test_utils::metadata(&[0, 1, 2, 3, 4]),
+					None
+				),
 				Ok(())
 			);
 
@@ -347,7 +978,10 @@ mod vote {
 				0
 			);
 			//Check history is added and balance is frozen.
-			assert_eq!(Voting::vote(RuntimeOrigin::signed(alice), 5, true, 0), Ok(()));
+			assert_eq!(
+				Voting::vote(RuntimeOrigin::signed(alice), 5, VoteKind::Aye, 0, None),
+				Ok(())
+			);
 			assert_eq!(<crate::pallet::VotingHistory<Test>>::get(alice).unwrap().len(), 1);
 			assert_eq!(
 				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
@@ -355,7 +989,10 @@ mod vote {
 			);
 
 			//Check history is added and frozen balance is increased.
-			assert_eq!(Voting::vote(RuntimeOrigin::signed(alice), 6, false, 1), Ok(()));
+			assert_eq!(
+				Voting::vote(RuntimeOrigin::signed(alice), 6, VoteKind::Nay, 1, None),
+				Ok(())
+			);
 			assert_eq!(<crate::pallet::VotingHistory<Test>>::get(alice).unwrap().len(), 2);
 			assert_eq!(
 				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
@@ -363,7 +1000,10 @@ mod vote {
 			);
 
 			// Checking frozen balance is not increased on this vote.
-			assert_eq!(Voting::vote(RuntimeOrigin::signed(alice), 3, true, 2), Ok(()));
+			assert_eq!(
+				Voting::vote(RuntimeOrigin::signed(alice), 3, VoteKind::Aye, 2, None),
+				Ok(())
+			);
 			assert_eq!(<crate::pallet::VotingHistory<Test>>::get(alice).unwrap().len(), 3);
 			assert_eq!(
 				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
@@ -379,32 +1019,52 @@ mod vote {
 
 			// Alice makes a proposal.
 			assert_eq!(
-				Voting::make_proposal(RuntimeOrigin::signed(alice), vec![0, 1, 2, 3]),
+				Voting::make_proposal(
+					RuntimeOrigin::signed(alice),
+					0,
+					test_utils::metadata(&[0, 1, 2, 3]),
+					None
+				),
 				Ok(())
 			);
 			// Assert that the correct event was deposited
-			System::assert_last_event(Event::ProposalCreated { proposal_id: 0 }.into());
+			System::assert_last_event(
+				Event::ProposalCreated {
+					proposal_id: 0,
+					description_hash: test_utils::description_hash(&[0, 1, 2, 3]),
+				}
+				.into(),
+			);
 
 			assert_eq!(
 				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
 				0
 			);
 
-			assert_eq!(Voting::vote(RuntimeOrigin::signed(alice), 5, true, 0), Ok(()));
+			assert_eq!(
+				Voting::vote(RuntimeOrigin::signed(alice), 5, VoteKind::Aye, 0, None),
+				Ok(())
+			);
 			assert_eq!(<crate::pallet::VotingHistory<Test>>::get(alice).unwrap().len(), 1);
 			assert_eq!(
 				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
 				25
 			);
 
-			assert_eq!(Voting::vote(RuntimeOrigin::signed(alice), 6, false, 0), Ok(()));
+			assert_eq!(
+				Voting::vote(RuntimeOrigin::signed(alice), 6, VoteKind::Nay, 0, None),
+				Ok(())
+			);
 			assert_eq!(<crate::pallet::VotingHistory<Test>>::get(alice).unwrap().len(), 1);
 			assert_eq!(
 				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
 				36
 			);
 
-			assert_eq!(Voting::vote(RuntimeOrigin::signed(alice), 3, false, 0), Ok(()));
+			assert_eq!(
+				Voting::vote(RuntimeOrigin::signed(alice), 3, VoteKind::Nay, 0, None),
+				Ok(())
+			);
 			assert_eq!(<crate::pallet::VotingHistory<Test>>::get(alice).unwrap().len(), 1);
 			assert_eq!(
 				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
@@ -420,40 +1080,71 @@ mod vote {
 
 			// Alice makes a proposal.
 			assert_eq!(
-				Voting::make_proposal(RuntimeOrigin::signed(alice), vec![0, 1, 2, 3]),
+				Voting::make_proposal(
+					RuntimeOrigin::signed(alice),
+					0,
+					test_utils::metadata(&[0, 1, 2, 3]),
+					None
+				),
 				Ok(())
 			);
 			// Assert that the correct event was deposited
-			System::assert_last_event(Event::ProposalCreated { proposal_id: 0 }.into());
+			System::assert_last_event(
+				Event::ProposalCreated {
+					proposal_id: 0,
+					description_hash: test_utils::description_hash(&[0, 1, 2, 3]),
+				}
+				.into(),
+			);
 
 			// Alice makes a proposal.
 			assert_eq!(
-				Voting::make_proposal(RuntimeOrigin::signed(alice), vec![0, 1, 2, 3]),
+				Voting::make_proposal(
+					RuntimeOrigin::signed(alice),
+					0,
+					test_utils::metadata(&[0, 1, 2, 3]),
+					None
+				),
 				Ok(())
 			);
 			// Assert that the correct event was deposited
-			System::assert_last_event(Event::ProposalCreated { proposal_id: 1 }.into());
+			System::assert_last_event(
+				Event::ProposalCreated {
+					proposal_id: 1,
+					description_hash: test_utils::description_hash(&[0, 1, 2, 3]),
+				}
+				.into(),
+			);
 
 			assert_eq!(
 				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
 				0
 			);
 
-			assert_eq!(Voting::vote(RuntimeOrigin::signed(alice), 5, true, 0), Ok(()));
+			assert_eq!(
+				Voting::vote(RuntimeOrigin::signed(alice), 5, VoteKind::Aye, 0, None),
+				Ok(())
+			);
 			assert_eq!(<crate::pallet::VotingHistory<Test>>::get(alice).unwrap().len(), 1);
 			assert_eq!(
 				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
 				25
 			);
 
-			assert_eq!(Voting::vote(RuntimeOrigin::signed(alice), 6, false, 0), Ok(()));
+			assert_eq!(
+				Voting::vote(RuntimeOrigin::signed(alice), 6, VoteKind::Nay, 0, None),
+				Ok(())
+			);
 			assert_eq!(<crate::pallet::VotingHistory<Test>>::get(alice).unwrap().len(), 1);
 			assert_eq!(
 				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
 				36
 			);
 
-			assert_eq!(Voting::vote(RuntimeOrigin::signed(alice), 6, false, 1), Ok(()));
+			assert_eq!(
+				Voting::vote(RuntimeOrigin::signed(alice), 6, VoteKind::Nay, 1, None),
+				Ok(())
+			);
 			assert_eq!(<crate::pallet::VotingHistory<Test>>::get(alice).unwrap().len(), 2);
 			assert_eq!(
 				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
@@ -461,7 +1152,10 @@ mod vote {
 			);
 
 			// Frozen balance remains unchanged because of the frozen amount on proposal 1.
-			assert_eq!(Voting::vote(RuntimeOrigin::signed(alice), 3, false, 0), Ok(()));
+			assert_eq!(
+				Voting::vote(RuntimeOrigin::signed(alice), 3, VoteKind::Nay, 0, None),
+				Ok(())
+			);
 			assert_eq!(<crate::pallet::VotingHistory<Test>>::get(alice).unwrap().len(), 2);
 			assert_eq!(
 				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
@@ -476,20 +1170,27 @@ mod vote {
 			for i in 0..100 {
 				assert_ok!(Voting::make_proposal(
 					RuntimeOrigin::signed(alice),
-					vec![0, 1, 2, 3, 4]
+					0,
+					test_utils::metadata(&[0, 1, 2, 3, 4]),
+					None
 				));
 				// Cast 1 aye.
-				assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 1, true, i));
+				assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 1, VoteKind::Aye, i, None));
 			}
 			assert_eq!(
 				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
 				1
 			);
 
-			assert_ok!(Voting::make_proposal(RuntimeOrigin::signed(alice), vec![0, 1, 2, 3, 4]));
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
 			// Vote for the 101th proposal fails.
 			assert_noop!(
-				Voting::vote(RuntimeOrigin::signed(alice), 1, true, 100),
+				Voting::vote(RuntimeOrigin::signed(alice), 1, VoteKind::Aye, 100, None),
 				Error::<Test>::TooManyVotes
 			);
 		});
@@ -502,13 +1203,23 @@ mod vote {
 
 			// Alice makes a proposal.
 			assert_eq!(
-				Voting::make_proposal(RuntimeOrigin::signed(alice), vec![0, 1, 2, 3]),
+				Voting::make_proposal(
+					RuntimeOrigin::signed(alice),
+					0,
+					test_utils::metadata(&[0, 1, 2, 3]),
+					None
+				),
 				Ok(())
 			);
 
 			// Alice makes a second proposal.
 			assert_eq!(
-				Voting::make_proposal(RuntimeOrigin::signed(alice), vec![0, 1, 2, 3]),
+				Voting::make_proposal(
+					RuntimeOrigin::signed(alice),
+					0,
+					test_utils::metadata(&[0, 1, 2, 3]),
+					None
+				),
 				Ok(())
 			);
 			assert_eq!(
@@ -516,28 +1227,40 @@ mod vote {
 				0
 			);
 
-			assert_eq!(Voting::vote(RuntimeOrigin::signed(alice), 5, true, 0), Ok(()));
+			assert_eq!(
+				Voting::vote(RuntimeOrigin::signed(alice), 5, VoteKind::Aye, 0, None),
+				Ok(())
+			);
 			assert_eq!(<crate::pallet::VotingHistory<Test>>::get(alice).unwrap().len(), 1);
 			assert_eq!(
 				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
 				25
 			);
 
-			assert_eq!(Voting::vote(RuntimeOrigin::signed(alice), 6, false, 0), Ok(()));
+			assert_eq!(
+				Voting::vote(RuntimeOrigin::signed(alice), 6, VoteKind::Nay, 0, None),
+				Ok(())
+			);
 			assert_eq!(<crate::pallet::VotingHistory<Test>>::get(alice).unwrap().len(), 1);
 			assert_eq!(
 				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
 				36
 			);
 
-			assert_eq!(Voting::vote(RuntimeOrigin::signed(alice), 3, false, 0), Ok(()));
+			assert_eq!(
+				Voting::vote(RuntimeOrigin::signed(alice), 3, VoteKind::Nay, 0, None),
+				Ok(())
+			);
 			assert_eq!(<crate::pallet::VotingHistory<Test>>::get(alice).unwrap().len(), 1);
 			assert_eq!(
 				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
 				9
 			);
 
-			assert_eq!(Voting::vote(RuntimeOrigin::signed(alice), 6, false, 1), Ok(()));
+			assert_eq!(
+				Voting::vote(RuntimeOrigin::signed(alice), 6, VoteKind::Nay, 1, None),
+				Ok(())
+			);
 			assert_eq!(<crate::pallet::VotingHistory<Test>>::get(alice).unwrap().len(), 2);
 			assert_eq!(
 				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
@@ -545,7 +1268,10 @@ mod vote {
 			);
 
 			// Frozen balance remains unchanged because of the frozen amount on proposal 1.
-			assert_eq!(Voting::vote(RuntimeOrigin::signed(alice), 0, false, 0), Ok(()));
+			assert_eq!(
+				Voting::vote(RuntimeOrigin::signed(alice), 0, VoteKind::Nay, 0, None),
+				Ok(())
+			);
 			// Voting history length shrinks because we have removed the vote
 			assert_eq!(<crate::pallet::VotingHistory<Test>>::get(alice).unwrap().len(), 1);
 			// Frozen amount remains unchanged, the one from proposal 1
@@ -563,14 +1289,22 @@ mod close_vote {
 	fn close_vote_aye_success() {
 		new_test_ext().execute_with(|| {
 			let (alice, bob) = test_utils::setup();
-			assert_ok!(Voting::make_proposal(RuntimeOrigin::signed(alice), vec![0, 1, 2, 3, 4]));
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
 			// Cast 1 aye.
-			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 1, true, 0));
-			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 1, true, 0));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 1, VoteKind::Aye, 0, None));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 1, VoteKind::Aye, 0, None));
 			System::set_block_number(11);
 			assert_ok!(Voting::end_vote(RuntimeOrigin::signed(bob), 0));
 			System::assert_last_event(Event::ProposalResultAye { proposal_id: 0 }.into());
-			assert_eq!(<crate::pallet::ProposalPool<Test>>::get(0).unwrap().end, true);
+			assert_eq!(
+				<crate::pallet::ProposalPool<Test>>::get(0).unwrap().status,
+				ProposalStatus::Passed
+			);
 		});
 	}
 
@@ -578,14 +1312,72 @@ mod close_vote {
 	fn close_vote_nay_success() {
 		new_test_ext().execute_with(|| {
 			let (alice, bob) = test_utils::setup();
-			assert_ok!(Voting::make_proposal(RuntimeOrigin::signed(alice), vec![0, 1, 2, 3, 4]));
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
 			// Cast 1 aye.
-			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 2, false, 0));
-			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 1, true, 0));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 2, VoteKind::Nay, 0, None));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 1, VoteKind::Aye, 0, None));
 			System::set_block_number(11);
 			assert_ok!(Voting::end_vote(RuntimeOrigin::signed(bob), 0));
 			System::assert_last_event(Event::ProposalResultNay { proposal_id: 0 }.into());
-			assert_eq!(<crate::pallet::ProposalPool<Test>>::get(0).unwrap().end, true);
+			assert_eq!(
+				<crate::pallet::ProposalPool<Test>>::get(0).unwrap().status,
+				ProposalStatus::Rejected
+			);
+		});
+	}
+
+	#[test]
+	fn proposal_status_lifecycle() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
+			let proposal = <crate::pallet::ProposalPool<Test>>::get(0).unwrap();
+			assert_eq!(proposal.status, ProposalStatus::Pending);
+			assert_eq!(proposal.closed_at, None);
+
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 1, VoteKind::Aye, 0, None));
+			assert_eq!(
+				<crate::pallet::ProposalPool<Test>>::get(0).unwrap().status,
+				ProposalStatus::Active
+			);
+
+			System::set_block_number(12);
+			assert_ok!(Voting::end_vote(RuntimeOrigin::signed(bob), 0));
+			let proposal = <crate::pallet::ProposalPool<Test>>::get(0).unwrap();
+			assert_eq!(proposal.status, ProposalStatus::Passed);
+			assert_eq!(proposal.closed_at, Some(12));
+			// The final tally is kept after the votes are claimed.
+			assert_ok!(Voting::claim_frozen_tokens(RuntimeOrigin::signed(bob), 0));
+			assert_eq!(<crate::pallet::ProposalPool<Test>>::get(0).unwrap().ayes, 1);
+		});
+	}
+
+	#[test]
+	fn close_vote_without_votes_expires() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
+			System::set_block_number(11);
+			assert_ok!(Voting::end_vote(RuntimeOrigin::signed(bob), 0));
+			assert_eq!(
+				<crate::pallet::ProposalPool<Test>>::get(0).unwrap().status,
+				ProposalStatus::Expired
+			);
 		});
 	}
 
@@ -593,14 +1385,22 @@ mod close_vote {
 	fn close_vote_tie_success() {
 		new_test_ext().execute_with(|| {
 			let (alice, bob) = test_utils::setup();
-			assert_ok!(Voting::make_proposal(RuntimeOrigin::signed(alice), vec![0, 1, 2, 3, 4]));
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
 			// Cast 1 aye.
-			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 1, true, 0));
-			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 1, false, 0));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 1, VoteKind::Aye, 0, None));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 1, VoteKind::Nay, 0, None));
 			System::set_block_number(11);
 			assert_ok!(Voting::end_vote(RuntimeOrigin::signed(bob), 0));
 			System::assert_last_event(Event::ProposalResultTie { proposal_id: 0 }.into());
-			assert_eq!(<crate::pallet::ProposalPool<Test>>::get(0).unwrap().end, true);
+			assert_eq!(
+				<crate::pallet::ProposalPool<Test>>::get(0).unwrap().status,
+				ProposalStatus::Tied
+			);
 		});
 	}
 
@@ -608,11 +1408,16 @@ mod close_vote {
 	fn close_vote_fail() {
 		new_test_ext().execute_with(|| {
 			let (alice, bob) = test_utils::setup();
-			assert_ok!(Voting::make_proposal(RuntimeOrigin::signed(alice), vec![0, 1, 2, 3, 4]));
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
 			// Cast 1 aye.
 
-			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 1, true, 0));
-			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 1, true, 0));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 1, VoteKind::Aye, 0, None));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 1, VoteKind::Aye, 0, None));
 			System::set_block_number(3);
 			assert_noop!(
 				Voting::end_vote(RuntimeOrigin::signed(bob), 0),
@@ -625,10 +1430,15 @@ mod close_vote {
 	fn close_vote_already_closed_fail() {
 		new_test_ext().execute_with(|| {
 			let (alice, bob) = test_utils::setup();
-			assert_ok!(Voting::make_proposal(RuntimeOrigin::signed(alice), vec![0, 1, 2, 3, 4]));
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
 			// Cast 1 aye.
-			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 1, true, 0));
-			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 1, true, 0));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 1, VoteKind::Aye, 0, None));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 1, VoteKind::Aye, 0, None));
 			System::set_block_number(11);
 			assert_ok!(Voting::end_vote(RuntimeOrigin::signed(bob), 0));
 			System::assert_last_event(Event::ProposalResultAye { proposal_id: 0 }.into());
@@ -643,10 +1453,15 @@ mod close_vote {
 	fn close_vote_proposal_doesnt_exist() {
 		new_test_ext().execute_with(|| {
 			let (alice, bob) = test_utils::setup();
-			assert_ok!(Voting::make_proposal(RuntimeOrigin::signed(alice), vec![0, 1, 2, 3, 4]));
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
 			// Cast 1 aye.
-			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 1, true, 0));
-			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 1, true, 0));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 1, VoteKind::Aye, 0, None));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 1, VoteKind::Aye, 0, None));
 			System::set_block_number(11);
 			assert_ok!(Voting::end_vote(RuntimeOrigin::signed(bob), 0));
 			System::assert_last_event(Event::ProposalResultAye { proposal_id: 0 }.into());
@@ -665,9 +1480,14 @@ mod claim_frozen_tokens {
 	fn voting_not_closed_fails() {
 		new_test_ext().execute_with(|| {
 			let (alice, _) = test_utils::setup();
-			assert_ok!(Voting::make_proposal(RuntimeOrigin::signed(alice), vec![0, 1, 2, 3, 4]));
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
 			// Cast 1 aye.
-			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 1, true, 0));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 1, VoteKind::Aye, 0, None));
 			assert_eq!(
 				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
 				1
@@ -700,9 +1520,14 @@ mod claim_frozen_tokens {
 	fn no_votes_fails() {
 		new_test_ext().execute_with(|| {
 			let (alice, bob) = test_utils::setup();
-			assert_ok!(Voting::make_proposal(RuntimeOrigin::signed(alice), vec![0, 1, 2, 3, 4]));
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
 
-			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 1, true, 0));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 1, VoteKind::Aye, 0, None));
 
 			System::set_block_number(11);
 			assert_ok!(Voting::end_vote(RuntimeOrigin::signed(bob), 0));
@@ -717,15 +1542,25 @@ mod claim_frozen_tokens {
 	fn claim_smaller_than_max() {
 		new_test_ext().execute_with(|| {
 			let (alice, _) = test_utils::setup();
-			assert_ok!(Voting::make_proposal(RuntimeOrigin::signed(alice), vec![0, 1, 2, 3, 4]));
-			assert_ok!(Voting::make_proposal(RuntimeOrigin::signed(alice), vec![0, 1, 2, 3, 4]));
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
 
-			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 1, true, 0));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 1, VoteKind::Aye, 0, None));
 			assert_eq!(
 				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
 				1
 			);
-			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 5, false, 1));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 5, VoteKind::Nay, 1, None));
 			assert_eq!(
 				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
 				25
@@ -734,7 +1569,9 @@ mod claim_frozen_tokens {
 			System::set_block_number(11);
 			assert_ok!(Voting::end_vote(RuntimeOrigin::signed(alice), 0));
 			assert_ok!(Voting::claim_frozen_tokens(RuntimeOrigin::signed(alice), 0));
-			System::assert_last_event(Event::NoTokensUnlocked.into());
+			System::assert_last_event(
+				Event::NoTokensUnlocked { who: alice, proposal_id: 0, frozen: 25 }.into(),
+			);
 			assert_eq!(
 				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
 				25
@@ -746,15 +1583,25 @@ mod claim_frozen_tokens {
 	fn claim_is_max() {
 		new_test_ext().execute_with(|| {
 			let (alice, _) = test_utils::setup();
-			assert_ok!(Voting::make_proposal(RuntimeOrigin::signed(alice), vec![0, 1, 2, 3, 4]));
-			assert_ok!(Voting::make_proposal(RuntimeOrigin::signed(alice), vec![0, 1, 2, 3, 4]));
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
 
-			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 1, true, 0));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 1, VoteKind::Aye, 0, None));
 			assert_eq!(
 				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
 				1
 			);
-			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 5, false, 1));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 5, VoteKind::Nay, 1, None));
 			assert_eq!(
 				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
 				25
@@ -763,7 +1610,9 @@ mod claim_frozen_tokens {
 			System::set_block_number(11);
 			assert_ok!(Voting::end_vote(RuntimeOrigin::signed(alice), 1));
 			assert_ok!(Voting::claim_frozen_tokens(RuntimeOrigin::signed(alice), 1));
-			System::assert_last_event(Event::TokensUnlocked.into());
+			System::assert_last_event(
+				Event::TokensUnlocked { who: alice, amount_released: 24, still_frozen: 1 }.into(),
+			);
 			assert_eq!(
 				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
 				1
@@ -775,15 +1624,25 @@ mod claim_frozen_tokens {
 	fn claim_thaws_last_proposal() {
 		new_test_ext().execute_with(|| {
 			let (alice, _) = test_utils::setup();
-			assert_ok!(Voting::make_proposal(RuntimeOrigin::signed(alice), vec![0, 1, 2, 3, 4]));
-			assert_ok!(Voting::make_proposal(RuntimeOrigin::signed(alice), vec![0, 1, 2, 3, 4]));
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
 
-			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 1, true, 0));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 1, VoteKind::Aye, 0, None));
 			assert_eq!(
 				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
 				1
 			);
-			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 5, false, 1));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 5, VoteKind::Nay, 1, None));
 			assert_eq!(
 				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
 				25
@@ -793,7 +1652,9 @@ mod claim_frozen_tokens {
 			assert_ok!(Voting::end_vote(RuntimeOrigin::signed(alice), 1));
 			assert_ok!(Voting::claim_frozen_tokens(RuntimeOrigin::signed(alice), 1));
 
-			System::assert_last_event(Event::TokensUnlocked.into());
+			System::assert_last_event(
+				Event::TokensUnlocked { who: alice, amount_released: 24, still_frozen: 1 }.into(),
+			);
 			assert_eq!(
 				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
 				1
@@ -810,7 +1671,10 @@ mod claim_frozen_tokens {
 
 			assert_ok!(Voting::end_vote(RuntimeOrigin::signed(alice), 0));
 			assert_ok!(Voting::claim_frozen_tokens(RuntimeOrigin::signed(alice), 0));
-			System::assert_last_event(Event::TokensUnlocked.into());
+			System::assert_last_event(
+				Event::TokensUnlocked { who: alice, amount_released: 1, still_frozen: 0 }.into(),
+			);
+			assert!(<crate::pallet::VotingHistory<Test>>::get(alice).is_none());
 			assert_eq!(
 				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
 				0
@@ -821,24 +1685,2787 @@ mod claim_frozen_tokens {
 			);
 		});
 	}
+
+	#[test]
+	fn claim_newest_proposal_keeps_most_expensive_vote_frozen() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			for _ in 0..6 {
+				assert_ok!(Voting::make_proposal(
+					RuntimeOrigin::signed(bob),
+					0,
+					test_utils::metadata(&[0, 1, 2, 3, 4]),
+					None
+				));
+			}
+
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 9, VoteKind::Aye, 0, None));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 1, VoteKind::Aye, 5, None));
+			assert_eq!(
+				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
+				81
+			);
+
+			System::set_block_number(11);
+			assert_ok!(Voting::end_vote(RuntimeOrigin::signed(alice), 5));
+			assert_ok!(Voting::claim_frozen_tokens(RuntimeOrigin::signed(alice), 5));
+			System::assert_last_event(
+				Event::NoTokensUnlocked { who: alice, proposal_id: 5, frozen: 81 }.into(),
+			);
+			assert_eq!(
+				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
+				81
+			);
+		});
+	}
+
+	#[test]
+	fn claim_in_any_order() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			for _ in 0..3 {
+				assert_ok!(Voting::make_proposal(
+					RuntimeOrigin::signed(bob),
+					0,
+					test_utils::metadata(&[0, 1, 2, 3, 4]),
+					None
+				));
+			}
+
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 3, VoteKind::Aye, 0, None));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 7, VoteKind::Nay, 1, None));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 5, VoteKind::Aye, 2, None));
+			assert_eq!(
+				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
+				49
+			);
+
+			System::set_block_number(11);
+			for proposal_id in 0..3 {
+				assert_ok!(Voting::end_vote(RuntimeOrigin::signed(alice), proposal_id));
+			}
+
+			// Claiming the most expensive vote in the middle of the history.
+			assert_ok!(Voting::claim_frozen_tokens(RuntimeOrigin::signed(alice), 1));
+			System::assert_last_event(
+				Event::TokensUnlocked { who: alice, amount_released: 24, still_frozen: 25 }.into(),
+			);
+			assert_ok!(Voting::claim_frozen_tokens(RuntimeOrigin::signed(alice), 0));
+			System::assert_last_event(
+				Event::NoTokensUnlocked { who: alice, proposal_id: 0, frozen: 25 }.into(),
+			);
+			assert_ok!(Voting::claim_frozen_tokens(RuntimeOrigin::signed(alice), 2));
+			System::assert_last_event(
+				Event::TokensUnlocked { who: alice, amount_released: 25, still_frozen: 0 }.into(),
+			);
+
+			// Every vote can only be claimed once.
+			assert_noop!(
+				Voting::claim_frozen_tokens(RuntimeOrigin::signed(alice), 1),
+				Error::<Test>::NoVotes
+			);
+		});
+	}
 }
 
-mod test_utils {
+mod migrations {
 	use super::*;
+	use crate::{
+		conviction::Conviction,
+		migrations::{
+			v1::{self, MigrateToV1},
+			v10::{self, DelegationV9, MigrateToV10},
+			v11::{self, MigrateToV11, UserVoteInfoV10},
+			v12::MigrateToV12,
+			v13::MigrateToV13,
+			v2::{self, CancelledProposals, MigrateToV2, OldProposal, ProposalV2},
+			v3::{self, MigrateToV3, ProposalV3},
+			v4::{self, MigrateToV4, ProposalV4},
+			v5::MigrateToV5,
+			v6::MigrateToV6,
+			v7::{self, MigrateToV7, UserVoteInfoV6},
+			v8::{self, MigrateToV8, UserVoteInfoV7},
+			v9::{self, MigrateToV9, UserVoteInfoV8},
+		},
+	};
+	use codec::Encode;
+	use frame_support::traits::{
+		fungible::MutateFreeze, GetStorageVersion, OnRuntimeUpgrade, QueryPreimage, StorageVersion,
+	};
 
-	pub fn setup() -> (u64, u64) {
-		// Go past genesis block so events get deposited.
-		System::set_block_number(1);
-		// Register two accounts.
-		let alice = 0;
-		let bob = 1;
-		// Give alice some tokens
-		assert_ok!(NativeBalance::mint_into(&alice, 100));
-		// give bob some tokens
-		assert_ok!(NativeBalance::mint_into(&bob, 100));
-		let _ = Voting::register_voters(RuntimeOrigin::root(), alice);
-		let _ = Voting::register_voters(RuntimeOrigin::root(), bob);
+	#[test]
+	fn migrate_to_v1_recomputes_freezes() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			StorageVersion::new(0).put::<Voting>();
+			for _ in 0..2 {
+				assert_ok!(Voting::make_proposal(
+					RuntimeOrigin::signed(bob),
+					0,
+					test_utils::metadata(&[0, 1, 2, 3, 4]),
+					None
+				));
+			}
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 9, VoteKind::Aye, 0, None));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 1, VoteKind::Aye, 1, None));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 2, VoteKind::Aye, 1, None));
 
-		(alice, bob)
+			// Voting histories as stored up to storage version 6.
+			for who in [alice, bob] {
+				let voting_history = <crate::pallet::VotingHistory<Test>>::take(who).unwrap();
+				v7::VotingHistory::<Test>::insert(
+					who,
+					frame_support::BoundedVec::truncate_from(
+						voting_history
+							.into_iter()
+							.map(|user_vote| UserVoteInfoV6::<Test> {
+								proposal_id: user_vote.proposal_id,
+								kind: user_vote.kind,
+								votes: user_vote.votes,
+							})
+							.collect::<Vec<_>>(),
+					),
+				);
+			}
+
+			// Freeze left too low by the old accounting and an empty history.
+			assert_ok!(NativeBalance::set_freeze(
+				&crate::FreezeReason::AccountDeposit.into(),
+				&alice,
+				1
+			));
+			v7::VotingHistory::<Test>::insert(2, frame_support::BoundedVec::default());
+
+			MigrateToV1::<Test>::on_runtime_upgrade();
+
+			assert_eq!(Voting::on_chain_storage_version(), 1);
+			assert_eq!(
+				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
+				81
+			);
+			assert_eq!(
+				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &bob),
+				4
+			);
+			assert!(!v7::VotingHistory::<Test>::contains_key(2));
+		});
+	}
+
+	#[test]
+	fn migrate_to_v1_adds_an_empty_call() {
+		new_test_ext().execute_with(|| {
+			StorageVersion::new(0).put::<Voting>();
+			// A proposal encoded as `{ description, start_block, ayes, nays, end }`.
+			let description = test_utils::description_hash(&[0, 1, 2, 3, 4]);
+			frame_support::storage::unhashed::put_raw(
+				&<crate::pallet::ProposalPool<Test>>::hashed_key_for(0),
+				&(description, 4u64, 3u128, 1u128, true).encode(),
+			);
+
+			MigrateToV1::<Test>::on_runtime_upgrade();
+
+			assert_eq!(Voting::on_chain_storage_version(), 1);
+			let proposal = v1::ProposalPool::<Test>::get(0).unwrap();
+			assert_eq!(proposal.description, description);
+			assert_eq!((proposal.start_block, proposal.ayes, proposal.nays), (4, 3, 1));
+			assert!(proposal.end);
+			assert!(proposal.call.is_none());
+
+			MigrateToV2::<Test>::on_runtime_upgrade();
+
+			assert_eq!(v2::ProposalPool::<Test>::get(0).unwrap().status, ProposalStatus::Passed);
+		});
+	}
+
+	#[test]
+	fn migrate_to_v2_sets_proposal_status() {
+		new_test_ext().execute_with(|| {
+			StorageVersion::new(1).put::<Voting>();
+			let old_proposal = |ayes: u128, nays: u128, end: bool| OldProposal::<Test> {
+				description: test_utils::description_hash(&[0, 1, 2, 3, 4]),
+				start_block: 1,
+				ayes,
+				nays,
+				end,
+				call: None,
+			};
+			let old_proposals = [
+				old_proposal(0, 0, false),
+				old_proposal(1, 0, false),
+				old_proposal(2, 1, true),
+				old_proposal(1, 2, true),
+				old_proposal(1, 1, true),
+				old_proposal(0, 0, true),
+				old_proposal(3, 0, true),
+			];
+			for (proposal_id, old) in old_proposals.iter().enumerate() {
+				frame_support::storage::unhashed::put_raw(
+					&<crate::pallet::ProposalPool<Test>>::hashed_key_for(proposal_id as u64),
+					&old.encode(),
+				);
+			}
+			CancelledProposals::<Test>::insert(6, ());
+
+			MigrateToV2::<Test>::on_runtime_upgrade();
+
+			assert_eq!(Voting::on_chain_storage_version(), 2);
+			let statuses = (0..7)
+				.map(|proposal_id| v2::ProposalPool::<Test>::get(proposal_id).unwrap().status)
+				.collect::<Vec<_>>();
+			assert_eq!(
+				statuses,
+				vec![
+					ProposalStatus::Pending,
+					ProposalStatus::Active,
+					ProposalStatus::Passed,
+					ProposalStatus::Rejected,
+					ProposalStatus::Tied,
+					ProposalStatus::Expired,
+					ProposalStatus::Cancelled,
+				]
+			);
+			assert_eq!(v2::ProposalPool::<Test>::get(2).unwrap().ayes, 2);
+			assert!(CancelledProposals::<Test>::get(6).is_none());
+		});
+	}
+
+	#[test]
+	fn migrate_to_v3_puts_proposals_on_first_track() {
+		new_test_ext().execute_with(|| {
+			StorageVersion::new(2).put::<Voting>();
+			v2::ProposalPool::<Test>::insert(
+				0,
+				ProposalV2::<Test> {
+					description: test_utils::description_hash(&[0, 1, 2, 3, 4]),
+					start_block: 4,
+					ayes: 3,
+					nays: 1,
+					status: ProposalStatus::Active,
+					closed_at: None,
+					call: None,
+				},
+			);
+
+			MigrateToV3::<Test>::on_runtime_upgrade();
+
+			assert_eq!(Voting::on_chain_storage_version(), 3);
+			let proposal = v3::ProposalPool::<Test>::get(0).unwrap();
+			assert_eq!(proposal.track, 0);
+			assert_eq!(proposal.start_block, 4);
+			assert_eq!(proposal.end_block, 13);
+			assert_eq!(proposal.ayes, 3);
+			assert_eq!(proposal.status, ProposalStatus::Active);
+		});
+	}
+
+	#[test]
+	fn migrate_to_v3_without_tracks_bumps_the_version() {
+		new_test_ext().execute_with(|| {
+			NoTracks::set(true);
+			StorageVersion::new(2).put::<Voting>();
+
+			MigrateToV3::<Test>::on_runtime_upgrade();
+
+			assert_eq!(Voting::on_chain_storage_version(), 3);
+		});
+	}
+
+	#[test]
+	fn migrate_to_v4_counts_voters() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			StorageVersion::new(3).put::<Voting>();
+			for proposal_id in 0..2 {
+				v3::ProposalPool::<Test>::insert(
+					proposal_id,
+					ProposalV3::<Test> {
+						description: test_utils::description_hash(&[0, 1, 2, 3, 4]),
+						track: 0,
+						start_block: 1,
+						end_block: 10,
+						ayes: 3,
+						nays: 0,
+						status: ProposalStatus::Active,
+						closed_at: None,
+						call: None,
+					},
+				);
+			}
+			let vote =
+				|proposal_id| UserVoteInfoV6::<Test> { kind: VoteKind::Aye, proposal_id, votes: 1 };
+			v7::VotingHistory::<Test>::insert(
+				alice,
+				frame_support::BoundedVec::truncate_from(vec![vote(0), vote(1)]),
+			);
+			v7::VotingHistory::<Test>::insert(
+				bob,
+				frame_support::BoundedVec::truncate_from(vec![vote(0)]),
+			);
+
+			MigrateToV4::<Test>::on_runtime_upgrade();
+
+			assert_eq!(Voting::on_chain_storage_version(), 4);
+			assert_eq!(v4::ProposalPool::<Test>::get(0).unwrap().voters, 2);
+			assert_eq!(v4::ProposalPool::<Test>::get(1).unwrap().voters, 1);
+		});
+	}
+
+	#[test]
+	fn migrate_to_v5_counts_registered_voters() {
+		new_test_ext().execute_with(|| {
+			StorageVersion::new(4).put::<Voting>();
+			for voter in [0, 1] {
+				frame_support::storage::unhashed::put(
+					&<crate::pallet::RegisteredAccounts<Test>>::hashed_key_for(voter),
+					&RegistrationKind::Manual,
+				);
+			}
+			assert_eq!(<crate::pallet::RegisteredAccounts<Test>>::count(), 0);
+
+			MigrateToV5::<Test>::on_runtime_upgrade();
+
+			assert_eq!(Voting::on_chain_storage_version(), 5);
+			assert_eq!(<crate::pallet::RegisteredAccounts<Test>>::count(), 2);
+		});
+	}
+
+	#[test]
+	fn migrate_to_v6_starts_tallies_without_abstentions() {
+		new_test_ext().execute_with(|| {
+			StorageVersion::new(5).put::<Voting>();
+			v4::ProposalPool::<Test>::insert(
+				0,
+				ProposalV4::<Test> {
+					description: test_utils::description_hash(&[0, 1, 2, 3, 4]),
+					track: 0,
+					start_block: 1,
+					end_block: 10,
+					ayes: 3,
+					nays: 1,
+					voters: 2,
+					status: ProposalStatus::Active,
+					closed_at: None,
+					call: None,
+				},
+			);
+
+			MigrateToV6::<Test>::on_runtime_upgrade();
+
+			assert_eq!(Voting::on_chain_storage_version(), 6);
+			let proposal = <crate::pallet::ProposalPool<Test>>::get(0).unwrap();
+			assert_eq!((proposal.ayes, proposal.nays, proposal.abstain), (3, 1, 0));
+			assert_eq!(proposal.voters, 2);
+			assert_eq!(proposal.status, ProposalStatus::Active);
+		});
+	}
+
+	#[test]
+	fn migrate_to_v7_makes_plain_votes() {
+		new_test_ext().execute_with(|| {
+			let (alice, _) = test_utils::setup();
+			StorageVersion::new(6).put::<Voting>();
+			let vote = UserVoteInfoV6::<Test> { proposal_id: 0, kind: VoteKind::Nay, votes: 3 };
+			v7::VotingHistory::<Test>::insert(
+				alice,
+				frame_support::BoundedVec::truncate_from(vec![vote]),
+			);
+
+			MigrateToV7::<Test>::on_runtime_upgrade();
+
+			assert_eq!(Voting::on_chain_storage_version(), 7);
+			let user_vote = &v8::VotingHistory::<Test>::get(alice).unwrap()[0];
+			assert_eq!(
+				(user_vote.proposal_id, user_vote.kind, user_vote.votes),
+				(0, VoteKind::Nay, 3)
+			);
+			assert_eq!(user_vote.conviction, None);
+			assert_eq!(user_vote.unlock_at, 0);
+		});
+	}
+
+	#[test]
+	fn migrate_to_v8_makes_native_votes() {
+		new_test_ext().execute_with(|| {
+			let (alice, _) = test_utils::setup();
+			StorageVersion::new(7).put::<Voting>();
+			let vote = UserVoteInfoV7::<Test> {
+				proposal_id: 0,
+				kind: VoteKind::Aye,
+				votes: 3,
+				conviction: Some(Conviction::Locked2x),
+				unlock_at: 30,
+			};
+			v8::VotingHistory::<Test>::insert(
+				alice,
+				frame_support::BoundedVec::truncate_from(vec![vote]),
+			);
+
+			MigrateToV8::<Test>::on_runtime_upgrade();
+
+			assert_eq!(Voting::on_chain_storage_version(), 8);
+			let user_vote = &v9::VotingHistory::<Test>::get(alice).unwrap()[0];
+			assert_eq!(
+				(user_vote.proposal_id, user_vote.kind, user_vote.votes),
+				(0, VoteKind::Aye, 3)
+			);
+			assert_eq!(user_vote.conviction, Some(Conviction::Locked2x));
+			assert_eq!(user_vote.unlock_at, 30);
+			assert_eq!(user_vote.asset, None);
+		});
+	}
+
+	#[test]
+	fn migrate_to_v9_locks_the_square_of_the_votes() {
+		new_test_ext().execute_with(|| {
+			let (alice, _) = test_utils::setup();
+			StorageVersion::new(8).put::<Voting>();
+			let vote = UserVoteInfoV8::<Test> {
+				proposal_id: 0,
+				kind: VoteKind::Split { aye: 2, nay: 1 },
+				votes: 3,
+				conviction: None,
+				unlock_at: 10,
+				asset: Some(COMMUNITY_ASSET),
+			};
+			v9::VotingHistory::<Test>::insert(
+				alice,
+				frame_support::BoundedVec::truncate_from(vec![vote]),
+			);
+
+			MigrateToV9::<Test>::on_runtime_upgrade();
+
+			assert_eq!(Voting::on_chain_storage_version(), 9);
+			let user_vote = &v11::VotingHistory::<Test>::get(alice).unwrap()[0];
+			assert_eq!(
+				(user_vote.proposal_id, user_vote.kind, user_vote.votes),
+				(0, VoteKind::Split { aye: 2, nay: 1 }, 3)
+			);
+			assert_eq!(user_vote.amount, 9);
+			assert_eq!(user_vote.conviction, None);
+			assert_eq!(user_vote.unlock_at, 10);
+			assert_eq!(user_vote.asset, Some(COMMUNITY_ASSET));
+		});
+	}
+
+	#[test]
+	fn migrate_to_v10_keeps_delegations() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			StorageVersion::new(9).put::<Voting>();
+			v10::Delegations::<Test>::insert(
+				bob,
+				DelegationV9::<Test> {
+					target: alice,
+					conviction: Conviction::Locked2x,
+					balance: 16,
+					votes: 8,
+					unlock_at: 20,
+				},
+			);
+
+			MigrateToV10::<Test>::on_runtime_upgrade();
+
+			assert_eq!(Voting::on_chain_storage_version(), 10);
+			assert_eq!(
+				<crate::pallet::Delegations<Test>>::get(bob),
+				Some(crate::Delegation {
+					target: alice,
+					conviction: Conviction::Locked2x,
+					balance: 16,
+					votes: 8,
+				})
+			);
+		});
+	}
+
+	#[test]
+	fn migrate_to_v11_counts_tallies_in_tenths() {
+		new_test_ext().execute_with(|| {
+			// The runtime counts whole votes, so plain votes used to round down to zero.
+			VotePrecision::set(1);
+			let (alice, bob) = test_utils::setup();
+			test_utils::make_proposals(bob, 3);
+			let set_tally = |proposal_id, ayes, nays| {
+				<crate::pallet::ProposalPool<Test>>::mutate(proposal_id, |proposal| {
+					let proposal = proposal.as_mut().unwrap();
+					(proposal.ayes, proposal.nays) = (ayes, nays);
+				})
+			};
+			// 2 ayes of alice with 3 delegated votes and a split vote of bob.
+			set_tally(0, 8, 5);
+			set_tally(1, 20, 10);
+			<crate::pallet::ProposalPool<Test>>::mutate(1, |proposal| {
+				proposal.as_mut().unwrap().status = ProposalStatus::Rejected;
+			});
+			// Ballots cast in an open round.
+			set_tally(2, 4, 1);
+			<crate::pallet::ProposalRound<Test>>::insert(2, 0);
+			<crate::pallet::Delegations<Test>>::insert(
+				2,
+				crate::Delegation {
+					target: alice,
+					conviction: Conviction::Locked1x,
+					balance: 9,
+					votes: 3,
+				},
+			);
+			<crate::pallet::DelegatedVotes<Test>>::insert(alice, 3);
+			let vote = |proposal_id, kind, votes, conviction| UserVoteInfoV10::<Test> {
+				proposal_id,
+				kind,
+				votes,
+				amount: votes * votes,
+				conviction,
+				unlock_at: 10,
+				asset: None,
+			};
+			StorageVersion::new(10).put::<Voting>();
+			v11::VotingHistory::<Test>::insert(
+				alice,
+				frame_support::BoundedVec::truncate_from(vec![
+					vote(0, VoteKind::Aye, 2, None),
+					vote(1, VoteKind::Aye, 10, Some(Conviction::Locked2x)),
+				]),
+			);
+			v11::VotingHistory::<Test>::insert(
+				bob,
+				frame_support::BoundedVec::truncate_from(vec![
+					vote(0, VoteKind::Split { aye: 3, nay: 5 }, 8, None),
+					vote(1, VoteKind::Nay, 10, None),
+				]),
+			);
+
+			MigrateToV11::<Test>::on_runtime_upgrade();
+
+			assert_eq!(Voting::on_chain_storage_version(), 11);
+			let convictions = |who| {
+				<crate::pallet::VotingHistory<Test>>::get(who)
+					.unwrap()
+					.iter()
+					.map(|user_vote| user_vote.conviction)
+					.collect::<Vec<_>>()
+			};
+			assert_eq!(convictions(alice), vec![Conviction::None, Conviction::Locked2x]);
+			assert_eq!(convictions(bob), vec![Conviction::None, Conviction::None]);
+			assert_eq!(<crate::pallet::Delegations<Test>>::get(2).unwrap().votes, 30);
+			assert_eq!(<crate::pallet::DelegatedVotes<Test>>::get(alice), 30);
+
+			// The open proposal is recounted in tenths without losing the plain votes.
+			assert_eq!(test_utils::tally(0), (35, 5));
+			// The closed proposal keeps its result, the ballots count as `Conviction::None`.
+			assert_eq!(test_utils::tally(1), (200, 100));
+			assert_eq!(test_utils::tally(2), (4, 1));
+		});
+	}
+
+	#[test]
+	fn migrate_to_v12_requests_the_preimages_of_open_proposals() {
+		new_test_ext().execute_with(|| {
+			let (alice, _) = test_utils::setup();
+			let texts = [vec![7u8; 1000], vec![8u8; 1000]];
+			for text in &texts {
+				assert_ok!(Preimage::note_preimage(RuntimeOrigin::signed(alice), text.clone()));
+				let hash = sp_core::blake2_256(text).into();
+				assert_ok!(Voting::make_proposal(
+					RuntimeOrigin::signed(alice),
+					0,
+					ProposalMetadata {
+						title: b"title".to_vec().try_into().unwrap(),
+						description: ProposalDescription::Preimage { hash, len: 1000 },
+					},
+					None
+				));
+			}
+			assert_ok!(Voting::cancel_proposal(RuntimeOrigin::root(), 1));
+			// Proposals made up to storage version 11 didn't request their preimages.
+			let hash = sp_core::blake2_256(&texts[0]).into();
+			<Preimage as QueryPreimage>::unrequest(&hash);
+			StorageVersion::new(11).put::<Voting>();
+
+			MigrateToV12::<Test>::on_runtime_upgrade();
+
+			assert_eq!(Voting::on_chain_storage_version(), 12);
+			assert!(<Preimage as QueryPreimage>::is_requested(&hash));
+			let hash = sp_core::blake2_256(&texts[1]).into();
+			assert!(!<Preimage as QueryPreimage>::is_requested(&hash));
+		});
+	}
+
+	#[test]
+	fn migrate_to_v13_records_the_hold_of_every_vote() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			test_utils::make_proposals(bob, 2);
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 3, VoteKind::Aye, 0, None));
+			VoteLockMode::set(crate::lock_mode::LockMode::CumulativeHold);
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 7, VoteKind::Aye, 0, None));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 5, VoteKind::Nay, 1, None));
+			// Up to storage version 12 the amount held for every vote wasn't recorded.
+			let _ = crate::VoteHolds::<Test>::clear_prefix(alice, u32::MAX, None);
+			StorageVersion::new(12).put::<Voting>();
+
+			MigrateToV13::<Test>::on_runtime_upgrade();
+
+			assert_eq!(Voting::on_chain_storage_version(), 13);
+			assert_eq!(crate::VoteHolds::<Test>::get(alice, 0), 49);
+			assert_eq!(crate::VoteHolds::<Test>::get(alice, 1), 25);
+			// Frozen tokens aren't recorded.
+			assert_eq!(crate::VoteHolds::<Test>::iter_prefix(bob).count(), 0);
+		});
+	}
+}
+
+mod enactment {
+	use super::*;
+
+	#[test]
+	fn passed_proposal_call_is_dispatched() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			let call = test_utils::set_storage_call();
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				Some(Preimage::bound(call).unwrap())
+			));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 2, VoteKind::Aye, 0, None));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 1, VoteKind::Nay, 0, None));
+			System::set_block_number(11);
+			assert_ok!(Voting::end_vote(RuntimeOrigin::signed(bob), 0));
+			System::assert_last_event(
+				Event::ProposalEnacted { proposal_id: 0, result: Ok(()) }.into(),
+			);
+			assert_eq!(
+				<crate::pallet::ProposalPool<Test>>::get(0).unwrap().status,
+				ProposalStatus::Enacted
+			);
+			// Nothing is dispatched before the enactment period is over.
+			assert_eq!(frame_support::storage::unhashed::get_raw(test_utils::STORAGE_KEY), None);
+
+			test_utils::run_scheduler_to(15);
+			assert_eq!(
+				frame_support::storage::unhashed::get_raw(test_utils::STORAGE_KEY),
+				Some(test_utils::STORAGE_VALUE.to_vec())
+			);
+		});
+	}
+
+	#[test]
+	fn rejected_proposal_call_is_not_dispatched() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			let call = test_utils::set_storage_call();
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				Some(Preimage::bound(call).unwrap())
+			));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 1, VoteKind::Aye, 0, None));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 2, VoteKind::Nay, 0, None));
+			System::set_block_number(11);
+			assert_ok!(Voting::end_vote(RuntimeOrigin::signed(bob), 0));
+			System::assert_last_event(Event::ProposalResultNay { proposal_id: 0 }.into());
+
+			test_utils::run_scheduler_to(15);
+			assert_eq!(frame_support::storage::unhashed::get_raw(test_utils::STORAGE_KEY), None);
+		});
+	}
+
+	#[test]
+	fn passed_proposal_without_call_is_not_enacted() {
+		new_test_ext().execute_with(|| {
+			let (alice, _) = test_utils::setup();
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 1, VoteKind::Aye, 0, None));
+			System::set_block_number(11);
+			assert_ok!(Voting::end_vote(RuntimeOrigin::signed(alice), 0));
+			System::assert_last_event(Event::ProposalResultAye { proposal_id: 0 }.into());
+		});
+	}
+
+	#[test]
+	fn call_must_be_allowed_by_the_track() {
+		new_test_ext().execute_with(|| {
+			let (alice, _) = test_utils::setup();
+			// The community track only allows remarks.
+			assert_noop!(
+				Voting::make_proposal(
+					RuntimeOrigin::signed(alice),
+					2,
+					test_utils::metadata(&[0, 1, 2, 3, 4]),
+					Some(Preimage::bound(test_utils::set_storage_call()).unwrap())
+				),
+				Error::<Test>::CallNotAllowed
+			);
+			let remark = RuntimeCall::System(frame_system::Call::remark { remark: vec![1] });
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				2,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				Some(Preimage::bound(remark).unwrap())
+			));
+
+			// Calls whose preimage hasn't been noted can't be checked.
+			assert_noop!(
+				Voting::make_proposal(
+					RuntimeOrigin::signed(alice),
+					0,
+					test_utils::metadata(&[0, 1, 2, 3, 4]),
+					Some(frame_support::traits::Bounded::Lookup {
+						hash: test_utils::description_hash(&[9]),
+						len: 10
+					})
+				),
+				Error::<Test>::CallUnavailable
+			);
+		});
+	}
+}
+
+mod auto_close {
+	use super::*;
+	use frame_support::weights::Weight;
+
+	#[test]
+	fn proposal_is_closed_when_voting_period_ends() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 2, VoteKind::Aye, 0, None));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 1, VoteKind::Nay, 0, None));
+			assert_eq!(<crate::pallet::ProposalsEndingAt<Test>>::get(10), vec![0]);
+
+			// Nothing happens before the voting period is over.
+			Voting::on_initialize(9);
+			assert!(<crate::pallet::ProposalPool<Test>>::get(0).unwrap().status.is_open());
+
+			System::set_block_number(10);
+			Voting::on_initialize(10);
+			System::assert_last_event(Event::ProposalResultAye { proposal_id: 0 }.into());
+			assert!(!<crate::pallet::ProposalPool<Test>>::get(0).unwrap().status.is_open());
+			assert!(<crate::pallet::ProposalsEndingAt<Test>>::get(10).is_empty());
+
+			// The proposal can't be closed twice.
+			assert_noop!(
+				Voting::end_vote(RuntimeOrigin::signed(alice), 0),
+				Error::<Test>::VoteAlreadyEnded
+			);
+			assert_ok!(Voting::claim_frozen_tokens(RuntimeOrigin::signed(alice), 0));
+		});
+	}
+
+	#[test]
+	fn proposals_over_the_limit_are_closed_on_idle() {
+		new_test_ext().execute_with(|| {
+			let (alice, _) = test_utils::setup();
+			for _ in 0..3 {
+				assert_ok!(Voting::make_proposal(
+					RuntimeOrigin::signed(alice),
+					0,
+					test_utils::metadata(&[0, 1, 2, 3, 4]),
+					None
+				));
+			}
+
+			// Only `MaxAutoClosePerBlock` proposals are closed in `on_initialize`.
+			System::set_block_number(10);
+			Voting::on_initialize(10);
+			assert!(!<crate::pallet::ProposalPool<Test>>::get(0).unwrap().status.is_open());
+			assert!(!<crate::pallet::ProposalPool<Test>>::get(1).unwrap().status.is_open());
+			assert!(<crate::pallet::ProposalPool<Test>>::get(2).unwrap().status.is_open());
+			assert_eq!(<crate::pallet::OverdueProposals<Test>>::get(), vec![2]);
+
+			// Without spare weight nothing is closed.
+			assert_eq!(Voting::on_idle(10, Weight::zero()), Weight::zero());
+			assert!(<crate::pallet::ProposalPool<Test>>::get(2).unwrap().status.is_open());
+
+			Voting::on_idle(10, Weight::MAX);
+			System::assert_last_event(Event::ProposalResultTie { proposal_id: 2 }.into());
+			assert!(!<crate::pallet::ProposalPool<Test>>::get(2).unwrap().status.is_open());
+			assert!(<crate::pallet::OverdueProposals<Test>>::get().is_empty());
+		});
+	}
+
+	#[test]
+	fn proposals_over_a_full_queue_are_closed_in_the_next_block() {
+		new_test_ext().execute_with(|| {
+			let (alice, _) = test_utils::setup();
+			test_utils::make_proposals(alice, 3);
+			System::set_block_number(2);
+			test_utils::make_proposals(alice, 1);
+			<crate::pallet::OverdueProposals<Test>>::put(frame_support::BoundedVec::truncate_from(
+				(100..228).collect::<Vec<_>>(),
+			));
+
+			System::set_block_number(10);
+			Voting::on_initialize(10);
+			assert!(<crate::pallet::ProposalPool<Test>>::get(2).unwrap().status.is_open());
+			assert_eq!(<crate::pallet::ProposalsEndingAt<Test>>::get(11), vec![2, 3]);
+
+			System::set_block_number(11);
+			Voting::on_initialize(11);
+			assert!(!<crate::pallet::ProposalPool<Test>>::get(2).unwrap().status.is_open());
+			assert!(!<crate::pallet::ProposalPool<Test>>::get(3).unwrap().status.is_open());
+		});
+	}
+
+	#[test]
+	fn manually_closed_proposal_is_skipped() {
+		new_test_ext().execute_with(|| {
+			let (alice, _) = test_utils::setup();
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
+			System::set_block_number(11);
+			assert_ok!(Voting::end_vote(RuntimeOrigin::signed(alice), 0));
+			System::reset_events();
+
+			Voting::on_initialize(10);
+			assert_eq!(System::events().len(), 0);
+		});
+	}
+
+	#[test]
+	fn too_many_proposals_ending_in_the_same_block_fails() {
+		new_test_ext().execute_with(|| {
+			let (alice, _) = test_utils::setup();
+			for _ in 0..128 {
+				assert_ok!(Voting::make_proposal(
+					RuntimeOrigin::signed(alice),
+					0,
+					test_utils::metadata(&[0, 1, 2, 3, 4]),
+					None
+				));
+			}
+			assert_noop!(
+				Voting::make_proposal(
+					RuntimeOrigin::signed(alice),
+					0,
+					test_utils::metadata(&[0, 1, 2, 3, 4]),
+					None
+				),
+				Error::<Test>::TooManyProposalsEnding
+			);
+
+			// Proposals made in the next block end in the next block.
+			System::set_block_number(2);
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
+		});
+	}
+}
+
+mod ballot_round {
+	use super::*;
+
+	/// Creates three proposals from alice and groups the first two into round 0, open until
+	/// block 6 with 25 voice credits per voter.
+	fn create_round(alice: u64) {
+		for _ in 0..3 {
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
+		}
+		assert_ok!(Voting::create_round(RuntimeOrigin::root(), vec![0, 1], 25, 5));
+		System::assert_last_event(Event::RoundCreated { round: 0, proposals: vec![0, 1] }.into());
+	}
+
+	#[test]
+	fn ballot_is_tallied_and_closed_with_the_round() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			create_round(alice);
+
+			// 3 * 3 + 4 * 4 = 25 voice credits.
+			assert_ok!(Voting::cast_ballot(
+				RuntimeOrigin::signed(alice),
+				0,
+				vec![(0, true, 3), (1, false, 4)]
+			));
+			System::assert_last_event(Event::BallotCast { round: 0, who: alice, cost: 25 }.into());
+			assert_ok!(Voting::cast_ballot(RuntimeOrigin::signed(bob), 0, vec![(1, true, 5)]));
+
+			// Voice credits are a separate budget, no tokens are frozen.
+			assert_eq!(
+				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
+				0
+			);
+
+			assert_noop!(
+				Voting::close_round(RuntimeOrigin::signed(alice), 0),
+				Error::<Test>::RoundNotOver
+			);
+			System::set_block_number(6);
+			assert_ok!(Voting::close_round(RuntimeOrigin::signed(alice), 0));
+			System::assert_has_event(Event::ProposalResultAye { proposal_id: 0 }.into());
+			System::assert_has_event(Event::ProposalResultAye { proposal_id: 1 }.into());
+			System::assert_last_event(Event::RoundClosed { round: 0 }.into());
+			assert!(!<crate::pallet::ProposalPool<Test>>::get(0).unwrap().status.is_open());
+			assert!(!<crate::pallet::ProposalPool<Test>>::get(1).unwrap().status.is_open());
+			assert!(<crate::pallet::ProposalPool<Test>>::get(2).unwrap().status.is_open());
+
+			assert_noop!(
+				Voting::close_round(RuntimeOrigin::signed(alice), 0),
+				Error::<Test>::RoundAlreadyClosed
+			);
+		});
+	}
+
+	#[test]
+	fn ballot_over_budget_fails() {
+		new_test_ext().execute_with(|| {
+			let (alice, _) = test_utils::setup();
+			create_round(alice);
+
+			// 4 * 4 + 4 * 4 > 25 voice credits.
+			assert_noop!(
+				Voting::cast_ballot(
+					RuntimeOrigin::signed(alice),
+					0,
+					vec![(0, true, 4), (1, false, 4)]
+				),
+				Error::<Test>::InsufficientVoiceCredits
+			);
+		});
+	}
+
+	#[test]
+	fn invalid_ballots_fail() {
+		new_test_ext().execute_with(|| {
+			let (alice, _) = test_utils::setup();
+			create_round(alice);
+
+			assert_noop!(
+				Voting::cast_ballot(RuntimeOrigin::signed(5), 0, vec![(0, true, 1)]),
+				Error::<Test>::NotRegistered
+			);
+			assert_noop!(
+				Voting::cast_ballot(RuntimeOrigin::signed(alice), 1, vec![(0, true, 1)]),
+				Error::<Test>::RoundDoesNotExist
+			);
+			assert_noop!(
+				Voting::cast_ballot(RuntimeOrigin::signed(alice), 0, vec![(2, true, 1)]),
+				Error::<Test>::ProposalNotInRound
+			);
+			assert_noop!(
+				Voting::cast_ballot(
+					RuntimeOrigin::signed(alice),
+					0,
+					vec![(0, true, 1), (0, false, 1)]
+				),
+				Error::<Test>::DuplicateProposal
+			);
+
+			assert_ok!(Voting::cast_ballot(RuntimeOrigin::signed(alice), 0, vec![(0, true, 1)]));
+			assert_noop!(
+				Voting::cast_ballot(RuntimeOrigin::signed(alice), 0, vec![(1, true, 1)]),
+				Error::<Test>::BallotAlreadyCast
+			);
+
+			System::set_block_number(6);
+			assert_noop!(
+				Voting::cast_ballot(RuntimeOrigin::signed(1), 0, vec![(0, true, 1)]),
+				Error::<Test>::RoundOver
+			);
+		});
+	}
+
+	#[test]
+	fn ballot_votes_count_like_votes_without_conviction() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			create_round(alice);
+			assert_ok!(Voting::cast_ballot(RuntimeOrigin::signed(bob), 0, vec![(0, true, 3)]));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 3, VoteKind::Aye, 2, None));
+			assert_eq!(test_utils::tally(0), test_utils::tally(2));
+		});
+	}
+
+	#[test]
+	fn ballots_need_votes_on_started_proposals() {
+		new_test_ext().execute_with(|| {
+			let (alice, _) = test_utils::setup();
+			create_round(alice);
+			assert_noop!(
+				Voting::cast_ballot(
+					RuntimeOrigin::signed(alice),
+					0,
+					vec![(0, true, 3), (1, false, 0)]
+				),
+				Error::<Test>::ZeroVotes
+			);
+
+			// The voting period of a proposal on the delayed track starts at block 6.
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				1,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
+			assert_ok!(Voting::create_round(RuntimeOrigin::root(), vec![3], 25, 10));
+			assert_noop!(
+				Voting::cast_ballot(RuntimeOrigin::signed(alice), 1, vec![(3, true, 1)]),
+				Error::<Test>::VotingNotStarted
+			);
+			System::set_block_number(6);
+			assert_ok!(Voting::cast_ballot(RuntimeOrigin::signed(alice), 1, vec![(3, true, 1)]));
+		});
+	}
+
+	#[test]
+	fn killed_proposals_stay_cancelled() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			create_round(alice);
+			assert_ok!(Voting::cast_ballot(RuntimeOrigin::signed(alice), 0, vec![(0, true, 3)]));
+			assert_ok!(Voting::kill_proposal(RuntimeOrigin::root(), 0));
+
+			assert_noop!(
+				Voting::cast_ballot(
+					RuntimeOrigin::signed(bob),
+					0,
+					vec![(0, true, 3), (1, true, 1)]
+				),
+				Error::<Test>::VoteAlreadyEnded
+			);
+			assert_ok!(Voting::cast_ballot(RuntimeOrigin::signed(bob), 0, vec![(1, true, 1)]));
+
+			System::set_block_number(6);
+			assert_ok!(Voting::close_round(RuntimeOrigin::signed(alice), 0));
+			assert_eq!(
+				<crate::pallet::ProposalPool<Test>>::get(0).unwrap().status,
+				ProposalStatus::Cancelled
+			);
+			assert_eq!(
+				<crate::pallet::ProposalPool<Test>>::get(1).unwrap().status,
+				ProposalStatus::Passed
+			);
+		});
+	}
+
+	#[test]
+	fn proposals_in_a_round_are_only_voted_and_closed_with_it() {
+		new_test_ext().execute_with(|| {
+			let (alice, _) = test_utils::setup();
+			create_round(alice);
+
+			assert_noop!(
+				Voting::vote(RuntimeOrigin::signed(alice), 1, VoteKind::Aye, 0, None),
+				Error::<Test>::ProposalInRound
+			);
+			System::set_block_number(10);
+			assert_noop!(
+				Voting::end_vote(RuntimeOrigin::signed(alice), 0),
+				Error::<Test>::ProposalInRound
+			);
+
+			// Only the proposal outside of the round is closed automatically.
+			Voting::on_initialize(10);
+			assert!(<crate::pallet::ProposalPool<Test>>::get(0).unwrap().status.is_open());
+			assert!(<crate::pallet::ProposalPool<Test>>::get(1).unwrap().status.is_open());
+			assert!(!<crate::pallet::ProposalPool<Test>>::get(2).unwrap().status.is_open());
+		});
+	}
+
+	#[test]
+	fn create_round_with_ineligible_proposals_fails() {
+		new_test_ext().execute_with(|| {
+			let (alice, _) = test_utils::setup();
+			create_round(alice);
+
+			assert_noop!(
+				Voting::create_round(RuntimeOrigin::signed(alice), vec![2], 25, 5),
+				DispatchError::BadOrigin
+			);
+			assert_noop!(
+				Voting::create_round(RuntimeOrigin::root(), vec![3], 25, 5),
+				Error::<Test>::ProposalDoesNotExist
+			);
+			assert_noop!(
+				Voting::create_round(RuntimeOrigin::root(), vec![1, 2], 25, 5),
+				Error::<Test>::ProposalNotEligibleForRound
+			);
+			assert_noop!(
+				Voting::create_round(RuntimeOrigin::root(), vec![2, 2], 25, 5),
+				Error::<Test>::DuplicateProposal
+			);
+			assert_noop!(
+				Voting::create_round(RuntimeOrigin::root(), vec![2, 3, 4, 5, 6], 25, 5),
+				Error::<Test>::TooManyProposalsInRound
+			);
+
+			// Proposals that already have votes can't join a round.
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 1, VoteKind::Aye, 2, None));
+			assert_noop!(
+				Voting::create_round(RuntimeOrigin::root(), vec![2], 25, 5),
+				Error::<Test>::ProposalNotEligibleForRound
+			);
+		});
+	}
+}
+
+mod runtime_api {
+	use super::*;
+	use crate::{conviction::Conviction, ProposalInfo, Tally, VoteInfo};
+	use sp_runtime::traits::Hash;
+
+	#[test]
+	fn queries_return_proposals_tallies_and_voter_state() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			for _ in 0..2 {
+				assert_ok!(Voting::make_proposal(
+					RuntimeOrigin::signed(alice),
+					0,
+					test_utils::metadata(&[0, 1, 2, 3, 4]),
+					None
+				));
+			}
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 3, VoteKind::Aye, 0, None));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 2, VoteKind::Nay, 0, None));
+			System::set_block_number(11);
+			assert_ok!(Voting::end_vote(RuntimeOrigin::signed(alice), 1));
+
+			assert_eq!(
+				Voting::proposal_info(0),
+				Some(ProposalInfo {
+					title: b"title".to_vec(),
+					text: Some(vec![0, 1, 2, 3, 4]),
+					description: <Test as frame_system::Config>::Hashing::hash(&[0, 1, 2, 3, 4]),
+					track: 0,
+					start_block: 1,
+					end_block: 10,
+					ayes: 3,
+					nays: 2,
+					abstain: 0,
+					voters: 2,
+					status: ProposalStatus::Active,
+					closed_at: None,
+					has_call: false,
+				})
+			);
+			assert_eq!(Voting::proposal_info(2), None);
+			assert_eq!(
+				Voting::proposal_info(1).map(|info| (info.status, info.closed_at)),
+				Some((ProposalStatus::Expired, Some(11)))
+			);
+			assert_eq!(Voting::tally(0), Some(Tally { ayes: 3, nays: 2, abstain: 0 }));
+			assert_eq!(Voting::active_proposals(), vec![0]);
+
+			assert_eq!(
+				Voting::voter_history(&alice),
+				vec![VoteInfo {
+					proposal_id: 0,
+					kind: VoteKind::Aye,
+					votes: 3,
+					amount: 9,
+					conviction: Conviction::None,
+					unlock_at: 10
+				}]
+			);
+			assert!(Voting::voter_history(&5).is_empty());
+			assert!(Voting::is_registered(&alice));
+			assert!(!Voting::is_registered(&5));
+			assert_eq!(Voting::vote_cost(3), Some(9));
+			assert_eq!(Voting::vote_cost(u128::MAX), None);
+			assert_eq!(Voting::frozen_balance(&alice), 9);
+		});
+	}
+}
+
+mod deposit {
+	use super::*;
+	use crate::conviction::Conviction;
+	use frame_support::traits::fungible::{Inspect, InspectHold};
+
+	fn deposit_reason() -> RuntimeHoldReason {
+		crate::HoldReason::ProposalDeposit.into()
+	}
+
+	#[test]
+	fn make_proposal_holds_deposit() {
+		new_test_ext().execute_with(|| {
+			ProposalDeposit::set(10);
+			let (alice, _) = test_utils::setup();
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
+			assert_eq!(NativeBalance::balance_on_hold(&deposit_reason(), &alice), 10);
+			assert_eq!(crate::ProposalDeposits::<Test>::get(0), Some((alice, 10)));
+		});
+	}
+
+	#[test]
+	fn make_proposal_fails_without_deposit_funds() {
+		new_test_ext().execute_with(|| {
+			ProposalDeposit::set(1_000);
+			let (alice, _) = test_utils::setup();
+			assert_noop!(
+				Voting::make_proposal(
+					RuntimeOrigin::signed(alice),
+					0,
+					test_utils::metadata(&[0, 1, 2, 3, 4]),
+					None
+				),
+				Error::<Test>::InsufficientDepositFunds
+			);
+		});
+	}
+
+	#[test]
+	fn deposit_refunded_with_enough_participation() {
+		new_test_ext().execute_with(|| {
+			ProposalDeposit::set(10);
+			let (alice, bob) = test_utils::setup();
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
+			// Votes without a conviction count at 0.1x for the participation too.
+			assert_ok!(Voting::vote(
+				RuntimeOrigin::signed(alice),
+				1,
+				VoteKind::Aye,
+				0,
+				Some(Conviction::Locked1x)
+			));
+			assert_ok!(Voting::vote(
+				RuntimeOrigin::signed(bob),
+				1,
+				VoteKind::Nay,
+				0,
+				Some(Conviction::Locked1x)
+			));
+			System::set_block_number(11);
+			assert_ok!(Voting::end_vote(RuntimeOrigin::signed(bob), 0));
+			System::assert_has_event(
+				Event::DepositRefunded { proposal_id: 0, who: alice, amount: 10 }.into(),
+			);
+			assert_eq!(NativeBalance::balance_on_hold(&deposit_reason(), &alice), 0);
+			assert_eq!(NativeBalance::balance(&alice), 100);
+			assert!(crate::ProposalDeposits::<Test>::get(0).is_none());
+		});
+	}
+
+	#[test]
+	fn deposit_slashed_with_low_participation() {
+		new_test_ext().execute_with(|| {
+			ProposalDeposit::set(10);
+			let (alice, bob) = test_utils::setup();
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 1, VoteKind::Aye, 0, None));
+			let issuance = NativeBalance::total_issuance();
+			System::set_block_number(11);
+			assert_ok!(Voting::end_vote(RuntimeOrigin::signed(bob), 0));
+			System::assert_has_event(
+				Event::DepositSlashed { proposal_id: 0, who: alice, amount: 10 }.into(),
+			);
+			assert_eq!(NativeBalance::balance_on_hold(&deposit_reason(), &alice), 0);
+			assert_eq!(NativeBalance::balance(&alice), 90);
+			assert_eq!(NativeBalance::total_issuance(), issuance - 10);
+		});
+	}
+
+	#[test]
+	fn kill_proposal_slashes_deposit() {
+		new_test_ext().execute_with(|| {
+			ProposalDeposit::set(10);
+			let (alice, bob) = test_utils::setup();
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 2, VoteKind::Nay, 0, None));
+			assert_ok!(Voting::kill_proposal(RuntimeOrigin::root(), 0));
+			System::assert_has_event(
+				Event::DepositSlashed { proposal_id: 0, who: alice, amount: 10 }.into(),
+			);
+			System::assert_last_event(Event::ProposalKilled { proposal_id: 0 }.into());
+			assert_eq!(
+				<crate::pallet::ProposalPool<Test>>::get(0).unwrap().status,
+				ProposalStatus::Cancelled
+			);
+			assert_eq!(NativeBalance::balance(&alice), 90);
+			// Voters get their tokens back right away.
+			assert_ok!(Voting::claim_frozen_tokens(RuntimeOrigin::signed(bob), 0));
+			assert_eq!(
+				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &bob),
+				0
+			);
+		});
+	}
+
+	#[test]
+	fn kill_proposal_fails() {
+		new_test_ext().execute_with(|| {
+			let (alice, _) = test_utils::setup();
+			assert_noop!(
+				Voting::kill_proposal(RuntimeOrigin::root(), 0),
+				Error::<Test>::ProposalDoesNotExist
+			);
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
+			assert_noop!(
+				Voting::kill_proposal(RuntimeOrigin::signed(alice), 0),
+				DispatchError::BadOrigin
+			);
+			assert_ok!(Voting::kill_proposal(RuntimeOrigin::root(), 0));
+			assert_noop!(
+				Voting::kill_proposal(RuntimeOrigin::root(), 0),
+				Error::<Test>::VoteAlreadyEnded
+			);
+		});
+	}
+}
+
+mod cancellation {
+	use super::*;
+
+	#[test]
+	fn withdraw_proposal_without_votes() {
+		new_test_ext().execute_with(|| {
+			ProposalDeposit::set(10);
+			let (alice, _) = test_utils::setup();
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
+			// Long after the grace period, but nobody voted yet.
+			System::set_block_number(8);
+			assert_ok!(Voting::withdraw_proposal(RuntimeOrigin::signed(alice), 0));
+			System::assert_has_event(
+				Event::DepositRefunded { proposal_id: 0, who: alice, amount: 10 }.into(),
+			);
+			System::assert_last_event(Event::ProposalWithdrawn { proposal_id: 0 }.into());
+			assert_eq!(
+				<crate::pallet::ProposalPool<Test>>::get(0).unwrap().status,
+				ProposalStatus::Cancelled
+			);
+			assert_eq!(NativeBalance::balance(&alice), 100);
+		});
+	}
+
+	#[test]
+	fn withdraw_proposal_with_votes_in_grace_period() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 2, VoteKind::Aye, 0, None));
+			System::set_block_number(3);
+			assert_ok!(Voting::withdraw_proposal(RuntimeOrigin::signed(alice), 0));
+			// Bob gets the tokens back before the voting period is over.
+			assert_ok!(Voting::claim_frozen_tokens(RuntimeOrigin::signed(bob), 0));
+			assert_eq!(
+				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &bob),
+				0
+			);
+			// The proposal is not closed again by the hooks.
+			System::set_block_number(10);
+			Voting::on_initialize(10);
+			assert!(!System::events().iter().any(|record| matches!(
+				record.event,
+				RuntimeEvent::Voting(Event::ProposalResultAye { .. })
+			)));
+		});
+	}
+
+	#[test]
+	fn withdraw_proposal_fails() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			assert_noop!(
+				Voting::withdraw_proposal(RuntimeOrigin::signed(alice), 0),
+				Error::<Test>::ProposalDoesNotExist
+			);
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
+			assert_noop!(
+				Voting::withdraw_proposal(RuntimeOrigin::signed(bob), 0),
+				Error::<Test>::NotProposer
+			);
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 1, VoteKind::Nay, 0, None));
+			System::set_block_number(4);
+			assert_noop!(
+				Voting::withdraw_proposal(RuntimeOrigin::signed(alice), 0),
+				Error::<Test>::WithdrawalPeriodOver
+			);
+			System::set_block_number(11);
+			assert_ok!(Voting::end_vote(RuntimeOrigin::signed(bob), 0));
+			assert_noop!(
+				Voting::withdraw_proposal(RuntimeOrigin::signed(alice), 0),
+				Error::<Test>::VoteAlreadyEnded
+			);
+		});
+	}
+
+	#[test]
+	fn cancel_proposal_refunds_deposit() {
+		new_test_ext().execute_with(|| {
+			ProposalDeposit::set(10);
+			let (alice, bob) = test_utils::setup();
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 1, VoteKind::Aye, 0, None));
+			assert_noop!(
+				Voting::cancel_proposal(RuntimeOrigin::signed(alice), 0),
+				DispatchError::BadOrigin
+			);
+			assert_ok!(Voting::cancel_proposal(RuntimeOrigin::root(), 0));
+			System::assert_has_event(
+				Event::DepositRefunded { proposal_id: 0, who: alice, amount: 10 }.into(),
+			);
+			System::assert_last_event(Event::ProposalCancelled { proposal_id: 0 }.into());
+			assert_eq!(NativeBalance::balance(&alice), 100);
+			assert_ok!(Voting::claim_frozen_tokens(RuntimeOrigin::signed(bob), 0));
+			assert_noop!(
+				Voting::cancel_proposal(RuntimeOrigin::root(), 0),
+				Error::<Test>::VoteAlreadyEnded
+			);
+		});
+	}
+}
+
+mod tracks {
+	use super::*;
+
+	#[test]
+	fn make_proposal_on_unknown_track_fails() {
+		new_test_ext().execute_with(|| {
+			let (alice, _) = test_utils::setup();
+			assert_noop!(
+				Voting::make_proposal(
+					RuntimeOrigin::signed(alice),
+					7,
+					test_utils::metadata(&[0, 1, 2, 3, 4]),
+					None
+				),
+				Error::<Test>::UnknownTrack
+			);
+		});
+	}
+
+	#[test]
+	fn voting_starts_after_track_delay() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				1,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
+			let proposal = <crate::pallet::ProposalPool<Test>>::get(0).unwrap();
+			assert_eq!((proposal.track, proposal.start_block, proposal.end_block), (1, 6, 9));
+
+			assert_noop!(
+				Voting::vote(RuntimeOrigin::signed(bob), 1, VoteKind::Aye, 0, None),
+				Error::<Test>::VotingNotStarted
+			);
+			System::set_block_number(6);
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 1, VoteKind::Aye, 0, None));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 1, VoteKind::Aye, 0, None));
+
+			System::set_block_number(8);
+			assert_noop!(
+				Voting::end_vote(RuntimeOrigin::signed(bob), 0),
+				Error::<Test>::VotingPeriodNotOver
+			);
+			System::set_block_number(9);
+			assert_ok!(Voting::end_vote(RuntimeOrigin::signed(bob), 0));
+			System::assert_last_event(Event::ProposalResultAye { proposal_id: 0 }.into());
+		});
+	}
+
+	#[test]
+	fn proposals_are_closed_at_the_end_of_their_track() {
+		new_test_ext().execute_with(|| {
+			let (alice, _) = test_utils::setup();
+			for track in [0, 1] {
+				assert_ok!(Voting::make_proposal(
+					RuntimeOrigin::signed(alice),
+					track,
+					test_utils::metadata(&[0, 1, 2, 3, 4]),
+					None
+				));
+			}
+			assert_eq!(crate::ProposalsEndingAt::<Test>::get(9).to_vec(), vec![1]);
+			assert_eq!(crate::ProposalsEndingAt::<Test>::get(10).to_vec(), vec![0]);
+
+			System::set_block_number(9);
+			Voting::on_initialize(9);
+			assert!(!<crate::pallet::ProposalPool<Test>>::get(1).unwrap().status.is_open());
+			assert!(<crate::pallet::ProposalPool<Test>>::get(0).unwrap().status.is_open());
+		});
+	}
+}
+
+mod quorum {
+	use super::*;
+	use sp_runtime::Perbill;
+
+	#[test]
+	fn proposal_below_quorum_has_no_result() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			MinVoters::set(2);
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 5, VoteKind::Aye, 0, None));
+
+			System::set_block_number(10);
+			assert_ok!(Voting::end_vote(RuntimeOrigin::signed(bob), 0));
+			System::assert_last_event(Event::ProposalResultNoQuorum { proposal_id: 0 }.into());
+			assert_eq!(
+				<crate::pallet::ProposalPool<Test>>::get(0).unwrap().status,
+				ProposalStatus::NoQuorum
+			);
+		});
+	}
+
+	#[test]
+	fn proposal_needs_approval_threshold_to_pass() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			MinApproval::set(Perbill::from_percent(70));
+			for _ in 0..2 {
+				assert_ok!(Voting::make_proposal(
+					RuntimeOrigin::signed(alice),
+					0,
+					test_utils::metadata(&[0, 1, 2, 3, 4]),
+					None
+				));
+			}
+			// 4 ayes out of 6 votes are below 70%, 5 out of 7 are above.
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 4, VoteKind::Aye, 0, None));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 2, VoteKind::Nay, 0, None));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 5, VoteKind::Aye, 1, None));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 2, VoteKind::Nay, 1, None));
+
+			System::set_block_number(10);
+			assert_ok!(Voting::end_vote(RuntimeOrigin::signed(bob), 0));
+			System::assert_last_event(Event::ProposalResultNay { proposal_id: 0 }.into());
+			assert_ok!(Voting::end_vote(RuntimeOrigin::signed(bob), 1));
+			System::assert_last_event(Event::ProposalResultAye { proposal_id: 1 }.into());
+			assert_eq!(
+				<crate::pallet::ProposalPool<Test>>::get(0).unwrap().status,
+				ProposalStatus::Rejected
+			);
+		});
+	}
+
+	#[test]
+	fn voters_are_counted_once() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
+			let voters = || <crate::pallet::ProposalPool<Test>>::get(0).unwrap().voters;
+
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 2, VoteKind::Aye, 0, None));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 3, VoteKind::Nay, 0, None));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 1, VoteKind::Aye, 0, None));
+			assert_eq!(voters(), 2);
+
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 0, VoteKind::Aye, 0, None));
+			assert_eq!(voters(), 1);
+			assert_ok!(Voting::deregister_voter(RuntimeOrigin::root(), bob));
+			assert_eq!(voters(), 0);
+		});
+	}
+}
+
+mod vote_threshold {
+	use super::*;
+	use crate::vote_threshold::VoteThreshold::{self, *};
+
+	fn approved(threshold: VoteThreshold, ayes: u128, nays: u128, voters: u32) -> bool {
+		threshold.approved(ayes, nays, voters, 100)
+	}
+
+	#[test]
+	fn simple_majority_ignores_turnout() {
+		assert!(approved(SimpleMajority, 3, 2, 1));
+		assert!(!approved(SimpleMajority, 2, 2, 100));
+		assert!(!approved(SimpleMajority, 2, 3, 100));
+	}
+
+	#[test]
+	fn super_majority_approve_needs_more_ayes_at_low_turnout() {
+		// 4 of 100 voters: nays / 2 < ayes / 10, more than 5 ayes per nay.
+		assert!(approved(SuperMajorityApprove, 11, 2, 4));
+		assert!(!approved(SuperMajorityApprove, 10, 2, 4));
+		// Full turnout: a simple majority.
+		assert!(approved(SuperMajorityApprove, 3, 2, 100));
+		assert!(!approved(SuperMajorityApprove, 2, 2, 100));
+		// Without voters nothing passes.
+		assert!(!approved(SuperMajorityApprove, 1, 0, 0));
+	}
+
+	#[test]
+	fn super_majority_against_needs_more_nays_at_low_turnout() {
+		// 4 of 100 voters: nays / 10 < ayes / 2, less than 5 nays per aye.
+		assert!(approved(SuperMajorityAgainst, 2, 9, 4));
+		assert!(!approved(SuperMajorityAgainst, 2, 10, 4));
+		// Full turnout: a simple majority.
+		assert!(approved(SuperMajorityAgainst, 3, 2, 100));
+		assert!(!approved(SuperMajorityAgainst, 2, 2, 100));
+	}
+
+	#[test]
+	fn thresholds_compare_exactly() {
+		// 4 of 9 voters: nays / 2 < ayes / 3, so 3 * nays < 2 * ayes.
+		assert!(!SuperMajorityApprove.approved(3u128, 2, 4, 9));
+		assert!(SuperMajorityApprove.approved(4u128, 2, 4, 9));
+		// Tallies whose products would overflow.
+		assert!(!SuperMajorityApprove.approved(u128::MAX, u128::MAX / 3 * 2, 4, 9));
+		assert!(SuperMajorityApprove.approved(u128::MAX, u128::MAX / 3 * 2 - 1, 4, 9));
+	}
+
+	#[test]
+	fn quadratic_tally_is_measured_against_registered_voters() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			assert_ok!(Voting::register_voters(RuntimeOrigin::root(), 2));
+			assert_ok!(Voting::register_voters(RuntimeOrigin::root(), 3));
+			assert_eq!(<crate::pallet::RegisteredAccounts<Test>>::count(), 4);
+			Threshold::set(SuperMajorityApprove);
+			for _ in 0..2 {
+				assert_ok!(Voting::make_proposal(
+					RuntimeOrigin::signed(alice),
+					0,
+					test_utils::metadata(&[0, 1, 2, 3, 4]),
+					None
+				));
+			}
+			// 2 of 4 voters: nays / 1 < ayes / 2. 3 votes lock 9 tokens.
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 3, VoteKind::Aye, 0, None));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 1, VoteKind::Nay, 0, None));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 3, VoteKind::Aye, 1, None));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 2, VoteKind::Nay, 1, None));
+
+			System::set_block_number(10);
+			assert_ok!(Voting::end_vote(RuntimeOrigin::signed(bob), 0));
+			System::assert_last_event(Event::ProposalResultAye { proposal_id: 0 }.into());
+			assert_ok!(Voting::end_vote(RuntimeOrigin::signed(bob), 1));
+			System::assert_last_event(Event::ProposalResultNay { proposal_id: 1 }.into());
+		});
+	}
+
+	#[test]
+	fn registered_voters_are_counted() {
+		new_test_ext().execute_with(|| {
+			let (_, bob) = test_utils::setup();
+			assert_ok!(Voting::register_voters_batch(RuntimeOrigin::root(), vec![2, 3, bob]));
+			assert_eq!(<crate::pallet::RegisteredAccounts<Test>>::count(), 4);
+			assert_ok!(Voting::deregister_voter(RuntimeOrigin::root(), bob));
+			assert_eq!(<crate::pallet::RegisteredAccounts<Test>>::count(), 3);
+		});
+	}
+}
+
+mod abstain {
+	use super::*;
+
+	#[test]
+	fn abstentions_count_for_quorum_but_not_approval() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			MinVoters::set(2);
+			for _ in 0..2 {
+				assert_ok!(Voting::make_proposal(
+					RuntimeOrigin::signed(alice),
+					0,
+					test_utils::metadata(&[0, 1, 2, 3, 4]),
+					None
+				));
+			}
+			// Bob's abstention makes the quorum, the ayes of alice pass the proposal.
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 2, VoteKind::Aye, 0, None));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 5, VoteKind::Abstain, 0, None));
+			// Without ayes or nays, abstentions alone are a tie.
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 2, VoteKind::Abstain, 1, None));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 3, VoteKind::Abstain, 1, None));
+
+			let proposal = <crate::pallet::ProposalPool<Test>>::get(0).unwrap();
+			assert_eq!((proposal.ayes, proposal.nays, proposal.abstain), (2, 0, 5));
+			assert_eq!(proposal.voters, 2);
+
+			System::set_block_number(10);
+			assert_ok!(Voting::end_vote(RuntimeOrigin::signed(bob), 0));
+			System::assert_last_event(Event::ProposalResultAye { proposal_id: 0 }.into());
+			assert_ok!(Voting::end_vote(RuntimeOrigin::signed(bob), 1));
+			System::assert_last_event(Event::ProposalResultTie { proposal_id: 1 }.into());
+			assert_eq!(
+				<crate::pallet::ProposalPool<Test>>::get(1).unwrap().status,
+				ProposalStatus::Tied
+			);
+		});
+	}
+
+	#[test]
+	fn split_vote_costs_the_square_of_all_votes() {
+		new_test_ext().execute_with(|| {
+			let (alice, _) = test_utils::setup();
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
+			assert_ok!(Voting::vote(
+				RuntimeOrigin::signed(alice),
+				3,
+				VoteKind::Split { aye: 2, nay: 1 },
+				0,
+				None
+			));
+
+			let proposal = <crate::pallet::ProposalPool<Test>>::get(0).unwrap();
+			assert_eq!((proposal.ayes, proposal.nays, proposal.abstain), (2, 1, 0));
+			assert_eq!(proposal.voters, 1);
+			assert_eq!(
+				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
+				9
+			);
+		});
+	}
+
+	#[test]
+	fn split_must_add_up_to_votes() {
+		new_test_ext().execute_with(|| {
+			let (alice, _) = test_utils::setup();
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
+			assert_noop!(
+				Voting::vote(
+					RuntimeOrigin::signed(alice),
+					3,
+					VoteKind::Split { aye: 2, nay: 2 },
+					0,
+					None
+				),
+				Error::<Test>::InvalidSplit
+			);
+			assert_noop!(
+				Voting::vote(
+					RuntimeOrigin::signed(alice),
+					u128::MAX,
+					VoteKind::Split { aye: u128::MAX, nay: 1 },
+					0,
+					None
+				),
+				Error::<Test>::InvalidSplit
+			);
+		});
+	}
+
+	#[test]
+	fn changing_a_split_vote_replaces_its_tally() {
+		new_test_ext().execute_with(|| {
+			let (alice, _) = test_utils::setup();
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				0,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
+			let tally = || {
+				let proposal = <crate::pallet::ProposalPool<Test>>::get(0).unwrap();
+				(proposal.ayes, proposal.nays, proposal.abstain, proposal.voters)
+			};
+
+			assert_ok!(Voting::vote(
+				RuntimeOrigin::signed(alice),
+				5,
+				VoteKind::Split { aye: 3, nay: 2 },
+				0,
+				None
+			));
+			assert_eq!(tally(), (3, 2, 0, 1));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 4, VoteKind::Abstain, 0, None));
+			assert_eq!(tally(), (0, 0, 4, 1));
+			assert_eq!(
+				Voting::voter_history(&alice),
+				vec![crate::VoteInfo {
+					proposal_id: 0,
+					kind: VoteKind::Abstain,
+					votes: 4,
+					amount: 16,
+					conviction: crate::conviction::Conviction::None,
+					unlock_at: 10
+				}]
+			);
+		});
+	}
+}
+
+mod conviction {
+	use super::*;
+	use crate::conviction::Conviction;
+
+	#[test]
+	fn conviction_multiplies_votes() {
+		assert_eq!(Conviction::None.votes(25u128), 25);
+		assert_eq!(Conviction::Locked1x.votes(25u128), 250);
+		assert_eq!(Conviction::Locked6x.votes(25u128), 1500);
+		assert_eq!(Conviction::Locked6x.votes(u128::MAX), u128::MAX);
+		assert_eq!(Conviction::None.lock_periods(), 0);
+		assert_eq!(Conviction::Locked3x.lock_periods(), 4);
+		assert_eq!(Conviction::Locked6x.lock_periods(), 32);
+	}
+
+	#[test]
+	fn tally_counts_convicted_votes() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			test_utils::make_proposals(alice, 1);
+			assert_ok!(Voting::vote(
+				RuntimeOrigin::signed(alice),
+				3,
+				VoteKind::Aye,
+				0,
+				Some(Conviction::Locked2x)
+			));
+			assert_ok!(Voting::vote(
+				RuntimeOrigin::signed(bob),
+				9,
+				VoteKind::Nay,
+				0,
+				Some(Conviction::None)
+			));
+
+			let proposal = <crate::pallet::ProposalPool<Test>>::get(0).unwrap();
+			assert_eq!((proposal.ayes, proposal.nays), (60, 9));
+			assert_eq!(proposal.voters, 2);
+			// The tokens frozen are the square of the votes, whatever the conviction.
+			assert_eq!(
+				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
+				9
+			);
+
+			// Changing the vote removes the votes counted with the old conviction. Votes cast
+			// without a conviction count as `Conviction::None`.
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 2, VoteKind::Aye, 0, None));
+			let proposal = <crate::pallet::ProposalPool<Test>>::get(0).unwrap();
+			assert_eq!((proposal.ayes, proposal.nays), (2, 9));
+			assert_eq!(
+				<crate::pallet::VotingHistory<Test>>::get(alice).unwrap()[0].conviction,
+				Conviction::None
+			);
+		});
+	}
+
+	#[test]
+	fn winning_votes_are_locked() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			test_utils::make_proposals(alice, 1);
+			assert_ok!(Voting::vote(
+				RuntimeOrigin::signed(alice),
+				3,
+				VoteKind::Aye,
+				0,
+				Some(Conviction::Locked3x)
+			));
+			assert_ok!(Voting::vote(
+				RuntimeOrigin::signed(bob),
+				1,
+				VoteKind::Nay,
+				0,
+				Some(Conviction::Locked6x)
+			));
+			assert_eq!(<crate::pallet::VotingHistory<Test>>::get(alice).unwrap()[0].unlock_at, 50);
+
+			System::set_block_number(10);
+			assert_ok!(Voting::end_vote(RuntimeOrigin::signed(bob), 0));
+			System::assert_last_event(Event::ProposalResultAye { proposal_id: 0 }.into());
+
+			// The losing vote isn't locked, the winning one is locked for 4 lock periods.
+			assert_ok!(Voting::claim_frozen_tokens(RuntimeOrigin::signed(bob), 0));
+			assert_noop!(
+				Voting::claim_frozen_tokens(RuntimeOrigin::signed(alice), 0),
+				Error::<Test>::VoteLocked
+			);
+			System::set_block_number(50);
+			assert_ok!(Voting::claim_frozen_tokens(RuntimeOrigin::signed(alice), 0));
+			assert_eq!(
+				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
+				0
+			);
+		});
+	}
+
+	#[test]
+	fn cancelled_proposals_are_not_locked() {
+		new_test_ext().execute_with(|| {
+			let (alice, _) = test_utils::setup();
+			test_utils::make_proposals(alice, 1);
+			assert_ok!(Voting::vote(
+				RuntimeOrigin::signed(alice),
+				3,
+				VoteKind::Aye,
+				0,
+				Some(Conviction::Locked6x)
+			));
+			assert_ok!(Voting::cancel_proposal(RuntimeOrigin::root(), 0));
+
+			assert_ok!(Voting::claim_frozen_tokens(RuntimeOrigin::signed(alice), 0));
+		});
+	}
+
+	#[test]
+	fn small_votes_without_conviction_are_counted() {
+		new_test_ext().execute_with(|| {
+			// Whole votes, as in the runtime.
+			VotePrecision::set(1);
+			let (alice, bob) = test_utils::setup();
+			test_utils::make_proposals(alice, 1);
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 1, VoteKind::Aye, 0, None));
+			assert_eq!(test_utils::tally(0), (1, 0));
+			// 15 tokens are worth 3 votes, counted at 0.1x.
+			assert_ok!(Voting::vote_with_amount(
+				RuntimeOrigin::signed(bob),
+				15,
+				VoteKind::Nay,
+				0,
+				None
+			));
+			assert_eq!(test_utils::tally(0), (1, 3));
+
+			System::set_block_number(10);
+			assert_ok!(Voting::end_vote(RuntimeOrigin::signed(bob), 0));
+			assert_eq!(Voting::proposal_info(0).unwrap().status, ProposalStatus::Rejected);
+		});
+	}
+
+	#[test]
+	fn abstain_and_split_votes_have_no_conviction() {
+		new_test_ext().execute_with(|| {
+			let (alice, _) = test_utils::setup();
+			test_utils::make_proposals(alice, 1);
+			assert_noop!(
+				Voting::vote(
+					RuntimeOrigin::signed(alice),
+					3,
+					VoteKind::Abstain,
+					0,
+					Some(Conviction::Locked1x)
+				),
+				Error::<Test>::InvalidConviction
+			);
+			assert_noop!(
+				Voting::vote(
+					RuntimeOrigin::signed(alice),
+					3,
+					VoteKind::Split { aye: 2, nay: 1 },
+					0,
+					Some(Conviction::Locked2x)
+				),
+				Error::<Test>::InvalidConviction
+			);
+			assert_ok!(Voting::vote(
+				RuntimeOrigin::signed(alice),
+				3,
+				VoteKind::Abstain,
+				0,
+				Some(Conviction::None)
+			));
+		});
+	}
+}
+
+mod delegation {
+	use super::*;
+	use crate::conviction::Conviction;
+
+	#[test]
+	fn delegated_votes_follow_the_delegate() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			test_utils::make_proposals(alice, 2);
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 2, VoteKind::Aye, 0, None));
+
+			// 16 tokens are worth 4 votes, doubled by the conviction.
+			assert_ok!(Voting::delegate(
+				RuntimeOrigin::signed(bob),
+				alice,
+				Conviction::Locked2x,
+				16
+			));
+			System::assert_last_event(
+				Event::Delegated { who: bob, target: alice, votes: 80 }.into(),
+			);
+			assert_eq!(
+				NativeBalance::balance_frozen(&crate::FreezeReason::Delegation.into(), &bob),
+				16
+			);
+			assert_eq!(test_utils::tally(0), (82, 0));
+
+			// Future votes of the delegate carry the delegated votes too.
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 1, VoteKind::Nay, 1, None));
+			assert_eq!(test_utils::tally(1), (0, 81));
+			assert_eq!(<crate::pallet::ProposalPool<Test>>::get(1).unwrap().voters, 1);
+
+			// Changing or removing a vote moves the delegated votes with it.
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 3, VoteKind::Nay, 0, None));
+			assert_eq!(test_utils::tally(0), (0, 83));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 0, VoteKind::Nay, 0, None));
+			assert_eq!(test_utils::tally(0), (0, 0));
+		});
+	}
+
+	#[test]
+	fn lock_periods_start_when_undelegating() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			test_utils::make_proposals(alice, 2);
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 2, VoteKind::Aye, 0, None));
+			assert_ok!(Voting::delegate(
+				RuntimeOrigin::signed(bob),
+				alice,
+				Conviction::Locked1x,
+				9
+			));
+			assert_eq!(test_utils::tally(0), (32, 0));
+
+			// The delegation outlives its lock period, which only starts once it's removed.
+			System::set_block_number(30);
+			assert_ok!(Voting::undelegate(RuntimeOrigin::signed(bob)));
+			System::assert_last_event(Event::Undelegated { who: bob, target: alice }.into());
+			assert_eq!(test_utils::tally(0), (2, 0));
+			assert_eq!(<crate::pallet::DelegationLocks<Test>>::get(bob), Some((40, 9)));
+			assert_eq!(
+				NativeBalance::balance_frozen(&crate::FreezeReason::Delegation.into(), &bob),
+				9
+			);
+			assert_noop!(
+				Voting::undelegate(RuntimeOrigin::signed(bob)),
+				Error::<Test>::NotDelegating
+			);
+
+			assert_noop!(
+				Voting::unlock_delegation(RuntimeOrigin::signed(bob)),
+				Error::<Test>::DelegationLocked
+			);
+			System::set_block_number(40);
+			assert_ok!(Voting::unlock_delegation(RuntimeOrigin::signed(bob)));
+			assert_eq!(
+				NativeBalance::balance_frozen(&crate::FreezeReason::Delegation.into(), &bob),
+				0
+			);
+			assert_noop!(
+				Voting::unlock_delegation(RuntimeOrigin::signed(bob)),
+				Error::<Test>::NothingToUnlock
+			);
+		});
+	}
+
+	#[test]
+	fn delegations_without_conviction_unlock_right_away() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			test_utils::make_proposals(alice, 2);
+			assert_ok!(Voting::delegate(RuntimeOrigin::signed(bob), alice, Conviction::None, 9));
+			assert_ok!(Voting::undelegate(RuntimeOrigin::signed(bob)));
+			assert!(!<crate::pallet::DelegationLocks<Test>>::contains_key(bob));
+			assert_eq!(
+				NativeBalance::balance_frozen(&crate::FreezeReason::Delegation.into(), &bob),
+				0
+			);
+		});
+	}
+
+	#[test]
+	fn deregistered_delegator_loses_its_delegation() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			test_utils::make_proposals(alice, 2);
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 2, VoteKind::Aye, 0, None));
+			assert_ok!(Voting::delegate(
+				RuntimeOrigin::signed(bob),
+				alice,
+				Conviction::Locked6x,
+				4
+			));
+			assert_eq!(test_utils::tally(0), (122, 0));
+
+			assert_ok!(Voting::deregister_voter(RuntimeOrigin::root(), bob));
+			assert_eq!(test_utils::tally(0), (2, 0));
+			assert!(!<crate::pallet::Delegations<Test>>::contains_key(bob));
+		});
+	}
+
+	#[test]
+	fn delegation_checks() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			test_utils::make_proposals(alice, 2);
+			assert_noop!(
+				Voting::delegate(RuntimeOrigin::signed(bob), bob, Conviction::None, 1),
+				Error::<Test>::InvalidDelegate
+			);
+			assert_noop!(
+				Voting::delegate(RuntimeOrigin::signed(bob), 5, Conviction::None, 1),
+				Error::<Test>::InvalidDelegate
+			);
+			assert_noop!(
+				Voting::delegate(RuntimeOrigin::signed(bob), alice, Conviction::None, 101),
+				Error::<Test>::InsufficientFunds
+			);
+
+			// Votes on open proposals would count twice.
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 1, VoteKind::Aye, 0, None));
+			assert_noop!(
+				Voting::delegate(RuntimeOrigin::signed(bob), alice, Conviction::None, 1),
+				Error::<Test>::HasOpenVotes
+			);
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 0, VoteKind::Aye, 0, None));
+
+			assert_ok!(Voting::delegate(RuntimeOrigin::signed(bob), alice, Conviction::None, 1));
+			assert_noop!(
+				Voting::delegate(RuntimeOrigin::signed(bob), alice, Conviction::None, 1),
+				Error::<Test>::AlreadyDelegating
+			);
+			assert_noop!(
+				Voting::vote(RuntimeOrigin::signed(bob), 1, VoteKind::Aye, 1, None),
+				Error::<Test>::AlreadyDelegating
+			);
+		});
+	}
+}
+
+mod assets {
+	use super::*;
+	use crate::{conviction::Conviction, AssetFreezes};
+	use frame_support::traits::{
+		fungibles,
+		tokens::{Fortitude, Preservation},
+	};
+
+	fn transferable(who: u64) -> u128 {
+		<Assets as fungibles::Inspect<u64>>::reducible_balance(
+			COMMUNITY_ASSET,
+			&who,
+			Preservation::Expendable,
+			Fortitude::Polite,
+		)
+	}
+
+	/// Gives alice 100 tokens of the community asset and makes a proposal on the community track
+	/// and one on the general track.
+	fn setup_community() -> (u64, u64) {
+		let (alice, bob) = test_utils::setup();
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), COMMUNITY_ASSET, alice, true, 1));
+		assert_ok!(<Assets as fungibles::Mutate<u64>>::mint_into(COMMUNITY_ASSET, &alice, 100));
+		for track in [2, 0] {
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(alice),
+				track,
+				test_utils::metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
+		}
+		(alice, bob)
+	}
+
+	#[test]
+	fn votes_on_community_track_freeze_the_asset() {
+		new_test_ext().execute_with(|| {
+			let (alice, _) = setup_community();
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 5, VoteKind::Aye, 0, None));
+
+			assert_eq!(AssetFreezes::<Test>::get(COMMUNITY_ASSET, alice), 25);
+			assert_eq!(Voting::frozen_balance(&alice), 0);
+			// The frozen tokens and the min balance of the asset can't be transferred.
+			assert_eq!(transferable(alice), 74);
+			let user_vote = &<crate::pallet::VotingHistory<Test>>::get(alice).unwrap()[0];
+			assert_eq!(user_vote.asset, Some(COMMUNITY_ASSET));
+
+			System::set_block_number(11);
+			assert_ok!(Voting::end_vote(RuntimeOrigin::signed(alice), 0));
+			assert_ok!(Voting::claim_frozen_tokens(RuntimeOrigin::signed(alice), 0));
+			System::assert_last_event(
+				Event::TokensUnlocked { who: alice, amount_released: 25, still_frozen: 0 }.into(),
+			);
+			assert!(!AssetFreezes::<Test>::contains_key(COMMUNITY_ASSET, alice));
+			assert_eq!(transferable(alice), 100);
+		});
+	}
+
+	#[test]
+	fn asset_and_native_freezes_are_separate() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = setup_community();
+			// Bob has native tokens but none of the asset.
+			assert_noop!(
+				Voting::vote(RuntimeOrigin::signed(bob), 1, VoteKind::Aye, 0, None),
+				Error::<Test>::InsufficientFunds
+			);
+
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 9, VoteKind::Aye, 0, None));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 9, VoteKind::Aye, 1, None));
+			assert_eq!(AssetFreezes::<Test>::get(COMMUNITY_ASSET, alice), 81);
+			assert_eq!(Voting::frozen_balance(&alice), 81);
+
+			// Removing the vote on the general track only thaws the native tokens.
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 0, VoteKind::Aye, 1, None));
+			assert_eq!(AssetFreezes::<Test>::get(COMMUNITY_ASSET, alice), 81);
+			assert_eq!(Voting::frozen_balance(&alice), 0);
+
+			// Deregistering removes the open vote and thaws the asset.
+			assert_ok!(Voting::deregister_voter(RuntimeOrigin::root(), alice));
+			assert!(!AssetFreezes::<Test>::contains_key(COMMUNITY_ASSET, alice));
+		});
+	}
+
+	#[test]
+	fn delegations_only_count_for_native_votes() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = setup_community();
+			assert_ok!(Voting::delegate(
+				RuntimeOrigin::signed(bob),
+				alice,
+				Conviction::Locked1x,
+				16
+			));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 2, VoteKind::Aye, 0, None));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 2, VoteKind::Aye, 1, None));
+
+			assert_eq!(<crate::pallet::ProposalPool<Test>>::get(0).unwrap().ayes, 2);
+			assert_eq!(<crate::pallet::ProposalPool<Test>>::get(1).unwrap().ayes, 42);
+		});
+	}
+}
+
+mod free_balance {
+	use super::*;
+	use crate::{conviction::Conviction, HoldReason};
+	use frame_support::traits::{fungible::MutateHold, tokens::Precision};
+
+	#[test]
+	fn held_tokens_cannot_be_frozen() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			test_utils::make_proposals(bob, 2);
+			assert_ok!(NativeBalance::hold(&HoldReason::ProposalDeposit.into(), &alice, 40));
+
+			// Alice owns the 64 tokens 8 votes cost, but only 59 of them are free to freeze.
+			assert_noop!(
+				Voting::vote(RuntimeOrigin::signed(alice), 8, VoteKind::Aye, 0, None),
+				Error::<Test>::InsufficientFreeBalance
+			);
+			assert_eq!(Voting::vote_shortfall(&alice, 0, 8), Some(5));
+			assert_eq!(Voting::vote_shortfall(&alice, 0, 7), Some(0));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 7, VoteKind::Aye, 0, None));
+			assert_eq!(Voting::frozen_balance(&alice), 49);
+
+			// Once the hold is released the tokens can be frozen.
+			assert_ok!(NativeBalance::release(
+				&HoldReason::ProposalDeposit.into(),
+				&alice,
+				40,
+				Precision::Exact
+			));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 8, VoteKind::Aye, 0, None));
+			assert_eq!(Voting::frozen_balance(&alice), 64);
+
+			// Delegations can't freeze held tokens either.
+			assert_ok!(NativeBalance::hold(&HoldReason::ProposalDeposit.into(), &bob, 40));
+			assert_noop!(
+				Voting::delegate(RuntimeOrigin::signed(bob), alice, Conviction::None, 60),
+				Error::<Test>::InsufficientFreeBalance
+			);
+			assert_ok!(Voting::delegate(RuntimeOrigin::signed(bob), alice, Conviction::None, 59));
+		});
+	}
+
+	#[test]
+	fn existential_deposit_cannot_be_frozen() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			test_utils::make_proposals(bob, 2);
+			// 10 votes cost every token of alice, including the existential deposit.
+			assert_noop!(
+				Voting::vote(RuntimeOrigin::signed(alice), 10, VoteKind::Aye, 0, None),
+				Error::<Test>::InsufficientFreeBalance
+			);
+			assert_eq!(Voting::vote_shortfall(&alice, 0, 10), Some(1));
+
+			// Alice doesn't own the 121 tokens 11 votes cost.
+			assert_noop!(
+				Voting::vote(RuntimeOrigin::signed(alice), 11, VoteKind::Aye, 0, None),
+				Error::<Test>::InsufficientFunds
+			);
+			assert_eq!(Voting::vote_shortfall(&alice, 0, 11), Some(22));
+			assert_eq!(Voting::vote_shortfall(&alice, 7, 1), None);
+		});
+	}
+
+	#[test]
+	fn frozen_tokens_can_back_other_votes() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			test_utils::make_proposals(bob, 2);
+			// Freezes overlap, so the tokens frozen for the first vote count for the second one.
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 7, VoteKind::Aye, 0, None));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 9, VoteKind::Nay, 1, None));
+			assert_eq!(Voting::frozen_balance(&alice), 81);
+		});
+	}
+}
+
+mod cumulative_hold {
+	use super::*;
+	use crate::{lock_mode::LockMode, HoldReason, VoteHolds};
+	use frame_support::traits::fungible::InspectHold;
+
+	fn held(who: u64) -> u128 {
+		NativeBalance::balance_on_hold(&HoldReason::Vote.into(), &who)
+	}
+
+	#[test]
+	fn vote_costs_add_up() {
+		new_test_ext().execute_with(|| {
+			VoteLockMode::set(LockMode::CumulativeHold);
+			let (alice, bob) = test_utils::setup();
+			test_utils::make_proposals(bob, 3);
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 7, VoteKind::Aye, 0, None));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 5, VoteKind::Aye, 1, None));
+			assert_eq!(held(alice), 74);
+			assert_eq!(Voting::frozen_balance(&alice), 74);
+			assert_eq!(
+				NativeBalance::balance_frozen(&crate::FreezeReason::AccountDeposit.into(), &alice),
+				0
+			);
+
+			// Only 25 tokens are left for a third vote.
+			assert_noop!(
+				Voting::vote(RuntimeOrigin::signed(alice), 6, VoteKind::Aye, 2, None),
+				Error::<Test>::InsufficientFreeBalance
+			);
+			assert_eq!(Voting::vote_shortfall(&alice, 2, 6), Some(11));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 5, VoteKind::Aye, 2, None));
+			assert_eq!(held(alice), 99);
+		});
+	}
+
+	#[test]
+	fn changing_a_vote_replaces_its_hold() {
+		new_test_ext().execute_with(|| {
+			VoteLockMode::set(LockMode::CumulativeHold);
+			let (alice, bob) = test_utils::setup();
+			test_utils::make_proposals(bob, 3);
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 7, VoteKind::Aye, 0, None));
+			// The 49 tokens of the old vote back the new one.
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 9, VoteKind::Nay, 0, None));
+			assert_eq!(held(alice), 81);
+
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 0, VoteKind::Nay, 0, None));
+			assert_eq!(held(alice), 0);
+		});
+	}
+
+	#[test]
+	fn claiming_releases_the_hold_of_the_vote() {
+		new_test_ext().execute_with(|| {
+			VoteLockMode::set(LockMode::CumulativeHold);
+			let (alice, bob) = test_utils::setup();
+			test_utils::make_proposals(bob, 3);
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 7, VoteKind::Aye, 0, None));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 5, VoteKind::Aye, 1, None));
+
+			System::set_block_number(11);
+			for proposal_id in [0, 1] {
+				assert_ok!(Voting::end_vote(RuntimeOrigin::signed(bob), proposal_id));
+			}
+			assert_ok!(Voting::claim_frozen_tokens(RuntimeOrigin::signed(alice), 0));
+			System::assert_last_event(
+				Event::TokensUnlocked { who: alice, amount_released: 49, still_frozen: 25 }.into(),
+			);
+			assert_eq!(held(alice), 25);
+
+			assert_ok!(Voting::claim_frozen_tokens(RuntimeOrigin::signed(alice), 1));
+			assert_eq!(held(alice), 0);
+		});
+	}
+
+	#[test]
+	fn switching_lock_mode_releases_the_old_lock() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			test_utils::make_proposals(bob, 3);
+			let freeze_reason = crate::FreezeReason::AccountDeposit.into();
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 7, VoteKind::Aye, 0, None));
+			assert_eq!(NativeBalance::balance_frozen(&freeze_reason, &alice), 49);
+
+			// The next vote thaws the freeze and holds the tokens of both votes.
+			VoteLockMode::set(LockMode::CumulativeHold);
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 5, VoteKind::Aye, 1, None));
+			assert_eq!(NativeBalance::balance_frozen(&freeze_reason, &alice), 0);
+			assert_eq!(held(alice), 74);
+
+			// Switching back releases the hold and freezes the most expensive vote.
+			VoteLockMode::set(LockMode::OverlappingFreeze);
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 6, VoteKind::Aye, 1, None));
+			assert_eq!(held(alice), 0);
+			assert_eq!(VoteHolds::<Test>::iter_prefix(alice).count(), 0);
+			assert_eq!(NativeBalance::balance_frozen(&freeze_reason, &alice), 49);
+		});
+	}
+
+	#[test]
+	fn shortfall_counts_the_hold_of_the_changed_vote() {
+		new_test_ext().execute_with(|| {
+			VoteLockMode::set(LockMode::CumulativeHold);
+			let (alice, bob) = test_utils::setup();
+			test_utils::make_proposals(bob, 2);
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 7, VoteKind::Aye, 0, None));
+
+			// The 49 tokens held for the vote back a new vote on the same proposal only.
+			assert_eq!(Voting::vote_shortfall(&alice, 1, 9), Some(31));
+			assert_noop!(
+				Voting::vote(RuntimeOrigin::signed(alice), 9, VoteKind::Aye, 1, None),
+				Error::<Test>::InsufficientFreeBalance
+			);
+			assert_eq!(Voting::vote_shortfall(&alice, 0, 10), Some(1));
+			assert_noop!(
+				Voting::vote(RuntimeOrigin::signed(alice), 10, VoteKind::Aye, 0, None),
+				Error::<Test>::InsufficientFreeBalance
+			);
+			assert_eq!(Voting::vote_shortfall(&alice, 0, 9), Some(0));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 9, VoteKind::Aye, 0, None));
+			assert_eq!(held(alice), 81);
+		});
+	}
+
+	#[test]
+	fn every_vote_has_its_own_hold() {
+		new_test_ext().execute_with(|| {
+			VoteLockMode::set(LockMode::CumulativeHold);
+			let (alice, bob) = test_utils::setup();
+			test_utils::make_proposals(bob, 2);
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 7, VoteKind::Aye, 0, None));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 5, VoteKind::Nay, 1, None));
+			assert_eq!(VoteHolds::<Test>::get(alice, 0), 49);
+			assert_eq!(VoteHolds::<Test>::get(alice, 1), 25);
+
+			// Changing a vote only changes the hold of its proposal.
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 3, VoteKind::Nay, 1, None));
+			assert_eq!(VoteHolds::<Test>::get(alice, 0), 49);
+			assert_eq!(VoteHolds::<Test>::get(alice, 1), 9);
+			assert_eq!(held(alice), 58);
+
+			// Claiming a vote releases exactly the amount held for its proposal.
+			System::set_block_number(11);
+			assert_ok!(Voting::end_vote(RuntimeOrigin::signed(bob), 1));
+			assert_ok!(Voting::claim_frozen_tokens(RuntimeOrigin::signed(alice), 1));
+			System::assert_last_event(
+				Event::TokensUnlocked { who: alice, amount_released: 9, still_frozen: 49 }.into(),
+			);
+			assert!(!VoteHolds::<Test>::contains_key(alice, 1));
+			assert_eq!(VoteHolds::<Test>::get(alice, 0), 49);
+			assert_eq!(held(alice), 49);
+		});
+	}
+}
+
+mod amount_votes {
+	use super::*;
+
+	#[test]
+	fn votes_are_the_square_root_of_the_amount() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			test_utils::make_proposals(bob, 1);
+			assert_ok!(Voting::vote_with_amount(
+				RuntimeOrigin::signed(alice),
+				20,
+				VoteKind::Aye,
+				0,
+				None
+			));
+			System::assert_last_event(Event::VoteAddedTo { proposal_id: 0, votes: 4 }.into());
+			assert_eq!(test_utils::tally(0), (4, 0));
+			// The exact amount is frozen, not the square of the votes.
+			assert_eq!(Voting::frozen_balance(&alice), 20);
+			assert_eq!(Voting::voter_history(&alice)[0].amount, 20);
+		});
+	}
+
+	#[test]
+	fn precision_keeps_a_fraction_of_the_votes() {
+		new_test_ext().execute_with(|| {
+			VotePrecision::set(100);
+			let (alice, bob) = test_utils::setup();
+			test_utils::make_proposals(bob, 1);
+			// The square root of 20 is 4.47.
+			assert_ok!(Voting::vote_with_amount(
+				RuntimeOrigin::signed(alice),
+				20,
+				VoteKind::Aye,
+				0,
+				None
+			));
+			// Whole votes are counted in hundredths.
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(bob), 4, VoteKind::Nay, 0, None));
+			assert_eq!(test_utils::tally(0), (447, 400));
+			assert_eq!(Voting::frozen_balance(&alice), 20);
+			assert_eq!(Voting::frozen_balance(&bob), 16);
+
+			System::set_block_number(11);
+			assert_ok!(Voting::end_vote(RuntimeOrigin::signed(bob), 0));
+			assert_eq!(Voting::proposal_info(0).unwrap().status, ProposalStatus::Passed);
+		});
+	}
+
+	#[test]
+	fn changing_the_amount_replaces_the_vote() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			test_utils::make_proposals(bob, 1);
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 3, VoteKind::Aye, 0, None));
+			assert_ok!(Voting::vote_with_amount(
+				RuntimeOrigin::signed(alice),
+				50,
+				VoteKind::Nay,
+				0,
+				None
+			));
+			assert_eq!(test_utils::tally(0), (0, 7));
+			assert_eq!(Voting::frozen_balance(&alice), 50);
+
+			assert_ok!(Voting::vote_with_amount(
+				RuntimeOrigin::signed(alice),
+				0,
+				VoteKind::Nay,
+				0,
+				None
+			));
+			System::assert_last_event(Event::VoteRemovedOrCancelled { proposal_id: 0 }.into());
+			assert_eq!(test_utils::tally(0), (0, 0));
+			assert_eq!(Voting::frozen_balance(&alice), 0);
+		});
+	}
+
+	#[test]
+	fn amount_must_be_affordable_and_not_split() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			test_utils::make_proposals(bob, 1);
+			assert_noop!(
+				Voting::vote_with_amount(RuntimeOrigin::signed(alice), 101, VoteKind::Aye, 0, None),
+				Error::<Test>::InsufficientFunds
+			);
+			assert_noop!(
+				Voting::vote_with_amount(
+					RuntimeOrigin::signed(alice),
+					16,
+					VoteKind::Split { aye: 2, nay: 2 },
+					0,
+					None
+				),
+				Error::<Test>::InvalidSplit
+			);
+		});
+	}
+}
+
+mod unlock_all {
+	use super::*;
+	use crate::conviction::Conviction;
+
+	#[test]
+	fn claims_every_ended_vote_at_once() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			test_utils::make_proposals(bob, 2);
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 3, VoteKind::Aye, 0, None));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 5, VoteKind::Nay, 1, None));
+			System::set_block_number(5);
+			test_utils::make_proposals(bob, 1);
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(alice), 2, VoteKind::Aye, 2, None));
+			assert_eq!(Voting::frozen_balance(&alice), 25);
+
+			System::set_block_number(11);
+			for proposal_id in [0, 1] {
+				assert_ok!(Voting::end_vote(RuntimeOrigin::signed(bob), proposal_id));
+			}
+			// Anyone can unlock the tokens of an account.
+			assert_ok!(Voting::unlock_all(RuntimeOrigin::signed(bob), alice));
+			System::assert_last_event(
+				Event::TokensUnlocked { who: alice, amount_released: 21, still_frozen: 4 }.into(),
+			);
+			assert_eq!(Voting::frozen_balance(&alice), 4);
+			let history = Voting::voter_history(&alice);
+			assert_eq!(history.len(), 1);
+			assert_eq!(history[0].proposal_id, 2);
+		});
+	}
+
+	#[test]
+	fn locked_votes_are_kept() {
+		new_test_ext().execute_with(|| {
+			let (alice, bob) = test_utils::setup();
+			assert_noop!(
+				Voting::unlock_all(RuntimeOrigin::signed(bob), alice),
+				Error::<Test>::NoVotes
+			);
+			test_utils::make_proposals(bob, 1);
+			assert_ok!(Voting::vote(
+				RuntimeOrigin::signed(alice),
+				3,
+				VoteKind::Aye,
+				0,
+				Some(Conviction::Locked1x)
+			));
+
+			System::set_block_number(11);
+			assert_ok!(Voting::end_vote(RuntimeOrigin::signed(bob), 0));
+			// The winning vote stays locked for one lock period.
+			assert_noop!(
+				Voting::unlock_all(RuntimeOrigin::signed(bob), alice),
+				Error::<Test>::NothingToUnlock
+			);
+
+			System::set_block_number(20);
+			assert_ok!(Voting::unlock_all(RuntimeOrigin::signed(bob), alice));
+			assert_eq!(Voting::frozen_balance(&alice), 0);
+			assert!(Voting::voter_history(&alice).is_empty());
+		});
+	}
+}
+
+mod test_utils {
+	use super::*;
+	use sp_core::H256;
+	use sp_runtime::traits::Hash;
+
+	pub fn setup() -> (u64, u64) {
+		// Go past genesis block so events get deposited.
+		System::set_block_number(1);
+		// Register two accounts.
+		let alice = 0;
+		let bob = 1;
+		// Give alice some tokens
+		assert_ok!(NativeBalance::mint_into(&alice, 100));
+		// give bob some tokens
+		assert_ok!(NativeBalance::mint_into(&bob, 100));
+		let _ = Voting::register_voters(RuntimeOrigin::root(), alice);
+		let _ = Voting::register_voters(RuntimeOrigin::root(), bob);
+
+		(alice, bob)
+	}
+
+	/// Metadata with a short title and a description stored in the pallet.
+	pub fn metadata(description: &[u8]) -> ProposalMetadata<Test> {
+		ProposalMetadata {
+			title: b"title".to_vec().try_into().unwrap(),
+			description: ProposalDescription::Inline(description.to_vec().try_into().unwrap()),
+		}
+	}
+
+	/// Makes `count` proposals from `proposer` on the general track, without a call.
+	pub fn make_proposals(proposer: u64, count: u32) {
+		for _ in 0..count {
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(proposer),
+				0,
+				metadata(&[0, 1, 2, 3, 4]),
+				None
+			));
+		}
+	}
+
+	/// The ayes and nays of a proposal.
+	pub fn tally(proposal_id: u64) -> (u128, u128) {
+		let proposal = <crate::pallet::ProposalPool<Test>>::get(proposal_id).unwrap();
+		(proposal.ayes, proposal.nays)
+	}
+
+	pub fn description_hash(description: &[u8]) -> H256 {
+		<Test as frame_system::Config>::Hashing::hash(description)
+	}
+
+	pub const STORAGE_KEY: &[u8] = b"voting_enactment";
+	pub const STORAGE_VALUE: &[u8] = b"enacted";
+
+	/// A root-only call whose effect can be checked in storage.
+	pub fn set_storage_call() -> RuntimeCall {
+		RuntimeCall::System(frame_system::Call::set_storage {
+			items: vec![(STORAGE_KEY.to_vec(), STORAGE_VALUE.to_vec())],
+		})
+	}
+
+	/// Runs the scheduler for every block up to and including `n`.
+	pub fn run_scheduler_to(n: u64) {
+		while System::block_number() < n {
+			System::set_block_number(System::block_number() + 1);
+			Scheduler::on_initialize(System::block_number());
+		}
 	}
 }
@@ -0,0 +1,54 @@
+//! Tracks grouping proposals that share a timeline, a participation threshold and a deposit.
+
+use crate::vote_threshold::VoteThreshold;
+use frame_support::sp_runtime::Perbill;
+use sp_std::vec::Vec;
+
+/// The track index type.
+pub type TrackId = u16;
+
+/// The parameters of a track.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TrackInfo<Balance, BlockNumber, AssetId> {
+	/// Name of the track, for front-ends.
+	pub name: &'static str,
+	/// Number of blocks between making a proposal and the start of its voting period.
+	pub start_delay: BlockNumber,
+	/// Number of blocks the voting period lasts. The proposal can't be closed before it is over.
+	pub duration: BlockNumber,
+	/// Min number of votes, ayes, nays and abstentions together, a proposal needs when it closes
+	/// for its deposit to be refunded. The deposit of proposals with less participation is
+	/// slashed.
+	pub min_participation: Balance,
+	/// Min number of distinct voters for the vote to be quorate. Proposals closed with fewer
+	/// voters don't pass, whatever their tally.
+	pub min_voters: u32,
+	/// Min share of ayes among the ayes and nays for a proposal to pass, on top of `threshold`.
+	/// Zero to only apply `threshold`.
+	pub min_approval: Perbill,
+	/// How the ayes a proposal needs to pass depend on the turnout.
+	pub threshold: VoteThreshold,
+	/// The deposit held from the proposer when a proposal is made.
+	pub deposit: Balance,
+	/// The asset whose tokens are frozen to vote on the proposals of the track, `None` for the
+	/// native token. Deposits are always held in the native token.
+	pub asset: Option<AssetId>,
+}
+
+/// The tracks proposals can be made on, and the calls their proposals can carry.
+pub trait TracksInfo<Balance, BlockNumber, AssetId, Call> {
+	/// Every track with its id. The first one is used for the proposals of the genesis config.
+	fn tracks() -> Vec<(TrackId, TrackInfo<Balance, BlockNumber, AssetId>)>;
+
+	/// The parameters of a track, or `None` if there is no track with this id.
+	fn info(id: TrackId) -> Option<TrackInfo<Balance, BlockNumber, AssetId>> {
+		Self::tracks()
+			.into_iter()
+			.find(|(track_id, _)| *track_id == id)
+			.map(|(_, info)| info)
+	}
+
+	/// Whether proposals on a track can carry `call`. Passed proposals dispatch their call with
+	/// the `EnactmentOrigin`, so tracks that are easy to pass should only allow harmless calls.
+	fn authorize_call(id: TrackId, call: &Call) -> bool;
+}
@@ -0,0 +1,131 @@
+//! Types returned by the runtime API of pallet-voting.
+
+use crate::conviction::Conviction;
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_std::vec::Vec;
+
+/// Where a proposal is in its lifecycle.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ProposalStatus {
+	/// Open for voting, nobody voted yet.
+	#[codec(index = 0)]
+	Pending,
+	/// Open for voting, votes have been cast.
+	#[codec(index = 1)]
+	Active,
+	/// Closed with enough ayes to reach the approval threshold of its track. The proposal has no
+	/// call or it couldn't be scheduled.
+	#[codec(index = 2)]
+	Passed,
+	/// Closed without enough ayes to reach the approval threshold of its track.
+	#[codec(index = 3)]
+	Rejected,
+	/// Closed with as many ayes as nays.
+	#[codec(index = 4)]
+	Tied,
+	/// Withdrawn, cancelled or killed before it could be closed.
+	#[codec(index = 5)]
+	Cancelled,
+	/// Passed and its call was handed to the scheduler.
+	#[codec(index = 6)]
+	Enacted,
+	/// Closed without any votes.
+	#[codec(index = 7)]
+	Expired,
+	/// Closed with votes from fewer voters than the quorum of its track.
+	#[codec(index = 8)]
+	NoQuorum,
+}
+
+impl ProposalStatus {
+	/// Whether the proposal can still be voted on.
+	pub fn is_open(&self) -> bool {
+		matches!(self, Self::Pending | Self::Active)
+	}
+}
+
+/// How the votes of a voter are cast on a proposal.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum VoteKind<Balance> {
+	/// Every vote against the proposal. The index matches the `aye` flag this type replaced.
+	#[codec(index = 0)]
+	Nay,
+	/// Every vote in approval of the proposal.
+	#[codec(index = 1)]
+	Aye,
+	/// Counted for the turnout, but neither in approval nor against the proposal.
+	#[codec(index = 2)]
+	Abstain,
+	/// The votes split between approval and rejection. `aye` and `nay` add up to the votes.
+	#[codec(index = 3)]
+	Split { aye: Balance, nay: Balance },
+}
+
+/// Information about a proposal.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ProposalInfo<Hash, Balance, BlockNumber> {
+	/// The title of the proposal.
+	pub title: Vec<u8>,
+	/// The description of the proposal, unless it was noted as a preimage.
+	pub text: Option<Vec<u8>>,
+	/// The hash of the proposal description.
+	pub description: Hash,
+	/// The track the proposal was made on.
+	pub track: u16,
+	/// The block the voting period starts in.
+	pub start_block: BlockNumber,
+	/// The block from which the proposal can be closed.
+	pub end_block: BlockNumber,
+	/// The number of votes in approval, in tenths of a vote like the rest of the tally.
+	pub ayes: Balance,
+	/// The number of votes in rejection.
+	pub nays: Balance,
+	/// The number of abstentions.
+	pub abstain: Balance,
+	/// The number of distinct voters.
+	pub voters: u32,
+	/// Where the proposal is in its lifecycle.
+	pub status: ProposalStatus,
+	/// The block the proposal was closed or cancelled in.
+	pub closed_at: Option<BlockNumber>,
+	/// Whether the proposal has a call to enact if it passes.
+	pub has_call: bool,
+}
+
+/// The votes cast on a proposal, in tenths of a vote.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Tally<Balance> {
+	/// The number of votes in approval.
+	pub ayes: Balance,
+	/// The number of votes in rejection.
+	pub nays: Balance,
+	/// The number of abstentions.
+	pub abstain: Balance,
+}
+
+/// A vote of a voter on a proposal.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct VoteInfo<ProposalId, Balance, BlockNumber> {
+	/// The proposal voted on.
+	pub proposal_id: ProposalId,
+	/// How the votes are cast.
+	pub kind: VoteKind<Balance>,
+	/// The number of votes in `VotePrecision` units, before the conviction.
+	pub votes: Balance,
+	/// The tokens locked for the vote.
+	pub amount: Balance,
+	/// The conviction of the vote, `Conviction::None` if it was cast without one.
+	pub conviction: Conviction,
+	/// The block from which the tokens can be claimed if the vote won.
+	pub unlock_at: BlockNumber,
+}
@@ -0,0 +1,73 @@
+//! Vote thresholds with adaptive quorum biasing.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::sp_runtime::traits::{AtLeast32BitUnsigned, IntegerSquareRoot, Zero};
+use scale_info::TypeInfo;
+
+/// How the ayes needed for a proposal to pass depend on the turnout, the number of distinct
+/// voters relative to the number of registered voters.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug, MaxEncodedLen, TypeInfo)]
+pub enum VoteThreshold {
+	/// Positive turnout bias: a low turnout needs a supermajority of ayes, with a full turnout a
+	/// simple majority is enough.
+	#[codec(index = 0)]
+	SuperMajorityApprove,
+	/// Negative turnout bias: a low turnout needs a supermajority of nays to reject the proposal,
+	/// with a full turnout a simple majority is enough.
+	#[codec(index = 1)]
+	SuperMajorityAgainst,
+	/// More ayes than nays, whatever the turnout.
+	#[codec(index = 2)]
+	SimpleMajority,
+}
+
+impl VoteThreshold {
+	/// Whether the ayes and nays cast by `voters` of the `electorate` registered voters pass the
+	/// threshold.
+	pub fn approved<Balance: AtLeast32BitUnsigned + Copy>(
+		&self,
+		ayes: Balance,
+		nays: Balance,
+		voters: u32,
+		electorate: u32,
+	) -> bool {
+		let sqrt_voters = Balance::from(voters).integer_sqrt();
+		let sqrt_electorate = Balance::from(electorate).integer_sqrt();
+
+		match *self {
+			Self::SimpleMajority => ayes > nays,
+			_ if sqrt_voters.is_zero() || sqrt_electorate.is_zero() => false,
+			// nays / sqrt(voters) < ayes / sqrt(electorate)
+			Self::SuperMajorityApprove => less_than(nays, sqrt_voters, ayes, sqrt_electorate),
+			// nays / sqrt(electorate) < ayes / sqrt(voters)
+			Self::SuperMajorityAgainst => less_than(nays, sqrt_electorate, ayes, sqrt_voters),
+		}
+	}
+}
+
+/// Whether `n1 / d1 < n2 / d2`, compared through their continued fractions so that nothing is
+/// multiplied and nothing can overflow. The denominators must not be zero.
+fn less_than<Balance: AtLeast32BitUnsigned + Copy>(
+	mut n1: Balance,
+	mut d1: Balance,
+	mut n2: Balance,
+	mut d2: Balance,
+) -> bool {
+	loop {
+		let q1 = n1 / d1;
+		let q2 = n2 / d2;
+		if q1 != q2 {
+			return q1 < q2
+		}
+		let r1 = n1 % d1;
+		let r2 = n2 % d2;
+		if r2.is_zero() {
+			return false
+		}
+		if r1.is_zero() {
+			return true
+		}
+		// n1 / d1 < n2 / d2 if and only if d2 / r2 < d1 / r1.
+		(n1, d1, n2, d2) = (d2, r2, d1, r1);
+	}
+}
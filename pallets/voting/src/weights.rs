@@ -0,0 +1,361 @@
+//! Placeholder weights for pallet_voting.
+//!
+//! These values were NOT measured. They are hand-written placeholders that only give every call
+//! a plausible base weight, a per-item cost and its database accesses, until the weights are
+//! generated by running the benchmarks on reference hardware (see the README). Don't deploy a
+//! production chain with them.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_voting.
+pub trait WeightInfo {
+	fn register_voters() -> Weight;
+	fn register_self() -> Weight;
+	fn make_proposal(d: u32, ) -> Weight;
+	fn vote(h: u32, ) -> Weight;
+	fn end_vote() -> Weight;
+	fn claim_frozen_tokens(h: u32, ) -> Weight;
+	fn on_initialize(p: u32, ) -> Weight;
+	fn on_idle(p: u32, ) -> Weight;
+	fn deregister_voter(h: u32, ) -> Weight;
+	fn suspend_voter() -> Weight;
+	fn set_registration_expiry() -> Weight;
+	fn set_registrar() -> Weight;
+	fn register_voters_batch(n: u32, ) -> Weight;
+	fn create_round(p: u32, ) -> Weight;
+	fn cast_ballot(p: u32, ) -> Weight;
+	fn close_round(p: u32, ) -> Weight;
+	fn kill_proposal() -> Weight;
+	fn withdraw_proposal() -> Weight;
+	fn cancel_proposal() -> Weight;
+	fn delegate(h: u32, ) -> Weight;
+	fn undelegate(h: u32, ) -> Weight;
+	fn unlock_all(h: u32, ) -> Weight;
+	fn unlock_delegation() -> Weight;
+}
+
+/// Placeholder weights for pallet_voting, using the database weights of the runtime.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn register_voters() -> Weight {
+		Weight::from_parts(15_000_000, 3517)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	fn register_self() -> Weight {
+		Weight::from_parts(17_000_000, 11003)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	fn make_proposal(d: u32, ) -> Weight {
+		Weight::from_parts(41_356_000, 3879)
+			.saturating_add(Weight::from_parts(1_512, 0).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	fn vote(h: u32, ) -> Weight {
+		Weight::from_parts(43_584_000, 8315)
+			.saturating_add(Weight::from_parts(182_409, 0).saturating_mul(h.into()))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	fn end_vote() -> Weight {
+		Weight::from_parts(28_000_000, 42428)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	fn claim_frozen_tokens(h: u32, ) -> Weight {
+		Weight::from_parts(35_917_000, 8315)
+			.saturating_add(Weight::from_parts(139_255, 0).saturating_mul(h.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	fn on_initialize(p: u32, ) -> Weight {
+		Weight::from_parts(6_244_000, 3879)
+			.saturating_add(Weight::from_parts(24_516_303, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().reads((6_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes((6_u64).saturating_mul(p.into())))
+			.saturating_add(Weight::from_parts(0, 41438).saturating_mul(p.into()))
+	}
+	fn on_idle(p: u32, ) -> Weight {
+		Weight::from_parts(4_108_000, 1887)
+			.saturating_add(Weight::from_parts(24_702_115, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((6_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((6_u64).saturating_mul(p.into())))
+			.saturating_add(Weight::from_parts(0, 41438).saturating_mul(p.into()))
+	}
+	fn deregister_voter(h: u32, ) -> Weight {
+		Weight::from_parts(31_720_000, 8315)
+			.saturating_add(Weight::from_parts(8_113_505, 0).saturating_mul(h.into()))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(h.into())))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(h.into())))
+			.saturating_add(Weight::from_parts(0, 2751).saturating_mul(h.into()))
+	}
+	fn suspend_voter() -> Weight {
+		Weight::from_parts(12_000_000, 3514)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn set_registration_expiry() -> Weight {
+		Weight::from_parts(11_000_000, 3514)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn set_registrar() -> Weight {
+		Weight::from_parts(8_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn register_voters_batch(n: u32, ) -> Weight {
+		Weight::from_parts(11_482_000, 3517)
+			.saturating_add(Weight::from_parts(4_317_892, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2524).saturating_mul(n.into()))
+	}
+	fn create_round(p: u32, ) -> Weight {
+		Weight::from_parts(12_104_000, 1489)
+			.saturating_add(Weight::from_parts(5_211_384, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(p.into())))
+			.saturating_add(Weight::from_parts(0, 2751).saturating_mul(p.into()))
+	}
+	fn cast_ballot(p: u32, ) -> Weight {
+		Weight::from_parts(23_617_000, 3635)
+			.saturating_add(Weight::from_parts(3_942_117, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(p.into())))
+			.saturating_add(Weight::from_parts(0, 2751).saturating_mul(p.into()))
+	}
+	fn close_round(p: u32, ) -> Weight {
+		Weight::from_parts(18_306_000, 3635)
+			.saturating_add(Weight::from_parts(28_613_092, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((7_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((6_u64).saturating_mul(p.into())))
+			.saturating_add(Weight::from_parts(0, 41438).saturating_mul(p.into()))
+	}
+	fn kill_proposal() -> Weight {
+		Weight::from_parts(39_281_000, 4414)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	fn withdraw_proposal() -> Weight {
+		Weight::from_parts(37_104_000, 4414)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	fn cancel_proposal() -> Weight {
+		Weight::from_parts(34_552_000, 4414)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	fn delegate(h: u32, ) -> Weight {
+		Weight::from_parts(39_402_000, 12115)
+			.saturating_add(Weight::from_parts(7_204_318, 0).saturating_mul(h.into()))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(h.into())))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(h.into())))
+			.saturating_add(Weight::from_parts(0, 2751).saturating_mul(h.into()))
+	}
+	fn undelegate(h: u32, ) -> Weight {
+		Weight::from_parts(35_118_000, 12115)
+			.saturating_add(Weight::from_parts(7_189_442, 0).saturating_mul(h.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(h.into())))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(h.into())))
+			.saturating_add(Weight::from_parts(0, 2751).saturating_mul(h.into()))
+	}
+	fn unlock_all(h: u32, ) -> Weight {
+		Weight::from_parts(37_604_000, 8315)
+			.saturating_add(Weight::from_parts(3_126_507, 0).saturating_mul(h.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(h.into())))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(Weight::from_parts(0, 2751).saturating_mul(h.into()))
+	}
+	fn unlock_delegation() -> Weight {
+		Weight::from_parts(22_000_000, 5574)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn register_voters() -> Weight {
+		Weight::from_parts(15_000_000, 3517)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn register_self() -> Weight {
+		Weight::from_parts(17_000_000, 11003)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn make_proposal(d: u32, ) -> Weight {
+		Weight::from_parts(41_356_000, 3879)
+			.saturating_add(Weight::from_parts(1_512, 0).saturating_mul(d.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	fn vote(h: u32, ) -> Weight {
+		Weight::from_parts(43_584_000, 8315)
+			.saturating_add(Weight::from_parts(182_409, 0).saturating_mul(h.into()))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	fn end_vote() -> Weight {
+		Weight::from_parts(28_000_000, 42428)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	fn claim_frozen_tokens(h: u32, ) -> Weight {
+		Weight::from_parts(35_917_000, 8315)
+			.saturating_add(Weight::from_parts(139_255, 0).saturating_mul(h.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn on_initialize(p: u32, ) -> Weight {
+		Weight::from_parts(6_244_000, 3879)
+			.saturating_add(Weight::from_parts(24_516_303, 0).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().reads((6_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().writes((6_u64).saturating_mul(p.into())))
+			.saturating_add(Weight::from_parts(0, 41438).saturating_mul(p.into()))
+	}
+	fn on_idle(p: u32, ) -> Weight {
+		Weight::from_parts(4_108_000, 1887)
+			.saturating_add(Weight::from_parts(24_702_115, 0).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((6_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((6_u64).saturating_mul(p.into())))
+			.saturating_add(Weight::from_parts(0, 41438).saturating_mul(p.into()))
+	}
+	fn deregister_voter(h: u32, ) -> Weight {
+		Weight::from_parts(31_720_000, 8315)
+			.saturating_add(Weight::from_parts(8_113_505, 0).saturating_mul(h.into()))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(h.into())))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(h.into())))
+			.saturating_add(Weight::from_parts(0, 2751).saturating_mul(h.into()))
+	}
+	fn suspend_voter() -> Weight {
+		Weight::from_parts(12_000_000, 3514)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn set_registration_expiry() -> Weight {
+		Weight::from_parts(11_000_000, 3514)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn set_registrar() -> Weight {
+		Weight::from_parts(8_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn register_voters_batch(n: u32, ) -> Weight {
+		Weight::from_parts(11_482_000, 3517)
+			.saturating_add(Weight::from_parts(4_317_892, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2524).saturating_mul(n.into()))
+	}
+	fn create_round(p: u32, ) -> Weight {
+		Weight::from_parts(12_104_000, 1489)
+			.saturating_add(Weight::from_parts(5_211_384, 0).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(p.into())))
+			.saturating_add(Weight::from_parts(0, 2751).saturating_mul(p.into()))
+	}
+	fn cast_ballot(p: u32, ) -> Weight {
+		Weight::from_parts(23_617_000, 3635)
+			.saturating_add(Weight::from_parts(3_942_117, 0).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(p.into())))
+			.saturating_add(Weight::from_parts(0, 2751).saturating_mul(p.into()))
+	}
+	fn close_round(p: u32, ) -> Weight {
+		Weight::from_parts(18_306_000, 3635)
+			.saturating_add(Weight::from_parts(28_613_092, 0).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((7_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((6_u64).saturating_mul(p.into())))
+			.saturating_add(Weight::from_parts(0, 41438).saturating_mul(p.into()))
+	}
+	fn kill_proposal() -> Weight {
+		Weight::from_parts(39_281_000, 4414)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	fn withdraw_proposal() -> Weight {
+		Weight::from_parts(37_104_000, 4414)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn cancel_proposal() -> Weight {
+		Weight::from_parts(34_552_000, 4414)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn delegate(h: u32, ) -> Weight {
+		Weight::from_parts(39_402_000, 12115)
+			.saturating_add(Weight::from_parts(7_204_318, 0).saturating_mul(h.into()))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(h.into())))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(h.into())))
+			.saturating_add(Weight::from_parts(0, 2751).saturating_mul(h.into()))
+	}
+	fn undelegate(h: u32, ) -> Weight {
+		Weight::from_parts(35_118_000, 12115)
+			.saturating_add(Weight::from_parts(7_189_442, 0).saturating_mul(h.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(h.into())))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(h.into())))
+			.saturating_add(Weight::from_parts(0, 2751).saturating_mul(h.into()))
+	}
+	fn unlock_all(h: u32, ) -> Weight {
+		Weight::from_parts(37_604_000, 8315)
+			.saturating_add(Weight::from_parts(3_126_507, 0).saturating_mul(h.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(h.into())))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(Weight::from_parts(0, 2751).saturating_mul(h.into()))
+	}
+	fn unlock_delegation() -> Weight {
+		Weight::from_parts(22_000_000, 5574)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+}
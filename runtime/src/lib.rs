@@ -7,9 +7,15 @@
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
 use codec::{Decode, Encode};
-use frame_support::traits::AsEnsureOriginWithArg;
+use frame_support::traits::{AsEnsureOriginWithArg, EqualPrivilegeOnly};
 use frame_system::{EnsureRoot, EnsureSigned};
 use pallet_grandpa::AuthorityId as GrandpaId;
+use pallet_voting::{
+	identity::{IdentityJudgement, JudgementLevel},
+	lock_mode::LockMode,
+	tracks::{TrackId, TrackInfo, TracksInfo},
+	vote_threshold::VoteThreshold,
+};
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
@@ -66,6 +72,9 @@ pub type Balance = u128;
 /// Index of a transaction in the chain.
 pub type Nonce = u32;
 
+/// Index of a proposal in pallet-voting.
+pub type ProposalId = u64;
+
 /// A hash of some data used by the chain.
 pub type Hash = sp_core::H256;
 
@@ -103,12 +112,12 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	// The version of the runtime specification. A full node will not attempt to use its native
 	//   runtime in substitute for the on-chain Wasm runtime unless all of `spec_name`,
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
-	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
+	// This value started at 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 1,
 };
 
@@ -246,7 +255,7 @@ impl pallet_balances::Config for Runtime {
 	type WeightInfo = pallet_balances::weights::SubstrateWeight<Runtime>;
 	type FreezeIdentifier = RuntimeFreezeReason;
 	type MaxFreezes = ConstU32<50>;
-	type RuntimeHoldReason = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
 	type MaxHolds = ConstU32<50>;
 }
 
@@ -291,7 +300,8 @@ impl pallet_assets::Config for Runtime {
 	type MetadataDepositPerByte = MetadataDepositPerByte;
 	type ApprovalDeposit = ApprovalDeposit;
 	type StringLimit = StringLimit;
-	type Freezer = ();
+	// Votes on tracks with an asset freeze it through pallet-voting.
+	type Freezer = Voting;
 	type Extra = ();
 	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
 	type RemoveItemsLimit = ConstU32<1000>;
@@ -300,15 +310,197 @@ impl pallet_assets::Config for Runtime {
 	type BenchmarkHelper = ();
 }
 
+parameter_types! {
+	pub const PreimageBaseDeposit: Balance = 1_000;
+	pub const PreimageByteDeposit: Balance = 1;
+}
+
+impl pallet_preimage::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = pallet_preimage::weights::SubstrateWeight<Runtime>;
+	type Currency = Balances;
+	type ManagerOrigin = EnsureRoot<AccountId>;
+	type BaseDeposit = PreimageBaseDeposit;
+	type ByteDeposit = PreimageByteDeposit;
+}
+
+parameter_types! {
+	pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) *
+		BlockWeights::get().max_block;
+}
+
+impl pallet_scheduler::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeOrigin = RuntimeOrigin;
+	type PalletsOrigin = OriginCaller;
+	type RuntimeCall = RuntimeCall;
+	type MaximumWeight = MaximumSchedulerWeight;
+	type ScheduleOrigin = EnsureRoot<AccountId>;
+	type MaxScheduledPerBlock = ConstU32<50>;
+	type WeightInfo = pallet_scheduler::weights::SubstrateWeight<Runtime>;
+	type OriginPrivilegeCmp = EqualPrivilegeOnly;
+	type Preimages = Preimage;
+}
+
+parameter_types! {
+	pub const BasicDeposit: Balance = 10_000;
+	pub const FieldDeposit: Balance = 1_000;
+	pub const SubAccountDeposit: Balance = 2_000;
+	pub const MaxSubAccounts: u32 = 100;
+	pub const MaxAdditionalFields: u32 = 100;
+	pub const MaxRegistrars: u32 = 20;
+}
+
+impl pallet_identity::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type BasicDeposit = BasicDeposit;
+	type FieldDeposit = FieldDeposit;
+	type SubAccountDeposit = SubAccountDeposit;
+	type MaxSubAccounts = MaxSubAccounts;
+	type MaxAdditionalFields = MaxAdditionalFields;
+	type MaxRegistrars = MaxRegistrars;
+	type Slashed = ();
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type RegistrarOrigin = EnsureRoot<AccountId>;
+	type WeightInfo = pallet_identity::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	/// Passed proposals are dispatched as root.
+	pub EnactmentOrigin: OriginCaller = frame_system::RawOrigin::Root.into();
+	/// Accounts can register themselves as voters once a registrar judged their identity.
+	pub const VoterJudgement: JudgementLevel = JudgementLevel::KnownGood;
+	/// The governance token of the community track. It has to be created in pallet-assets before
+	/// the proposals of the track can be voted on.
+	pub const CommunityAsset: u32 = 1;
+	/// The votes of an account overlap, the tokens of its most expensive vote back all the others.
+	pub const VoteLockMode: LockMode = LockMode::OverlappingFreeze;
+}
+
+/// The tracks of pallet-voting. Proposals that change the runtime start later and run longer, so
+/// that node operators have time to react, and need a larger deposit, more voters and a 2/3
+/// supermajority. Treasury and runtime proposals need a larger supermajority when few of the
+/// registered voters turn out. Community proposals are voted on with the tokens of the
+/// `CommunityAsset` instead of the native token.
+///
+/// Passed proposals are dispatched as root, so only the runtime track can carry any call. Signal
+/// proposals can only carry remarks, treasury proposals balance calls and community proposals
+/// asset calls.
+pub struct VotingTracks;
+
+impl TracksInfo<Balance, BlockNumber, u32, RuntimeCall> for VotingTracks {
+	fn tracks() -> Vec<(TrackId, TrackInfo<Balance, BlockNumber, u32>)> {
+		vec![
+			(
+				0,
+				TrackInfo {
+					name: "signal",
+					start_delay: 0,
+					duration: 90000,
+					min_participation: 10,
+					min_voters: 3,
+					min_approval: Perbill::from_percent(50),
+					threshold: VoteThreshold::SimpleMajority,
+					deposit: 100 * EXISTENTIAL_DEPOSIT,
+					asset: None,
+				},
+			),
+			(
+				1,
+				TrackInfo {
+					name: "treasury",
+					start_delay: DAYS,
+					duration: 7 * DAYS,
+					min_participation: 50,
+					min_voters: 5,
+					min_approval: Perbill::from_percent(60),
+					threshold: VoteThreshold::SuperMajorityApprove,
+					deposit: 500 * EXISTENTIAL_DEPOSIT,
+					asset: None,
+				},
+			),
+			(
+				2,
+				TrackInfo {
+					name: "runtime",
+					start_delay: 2 * DAYS,
+					duration: 14 * DAYS,
+					min_participation: 100,
+					min_voters: 10,
+					min_approval: Perbill::from_rational(2u32, 3u32),
+					threshold: VoteThreshold::SuperMajorityApprove,
+					deposit: 1000 * EXISTENTIAL_DEPOSIT,
+					asset: None,
+				},
+			),
+			(
+				3,
+				TrackInfo {
+					name: "community",
+					start_delay: 0,
+					duration: 7 * DAYS,
+					min_participation: 10,
+					min_voters: 3,
+					min_approval: Perbill::from_percent(50),
+					threshold: VoteThreshold::SimpleMajority,
+					deposit: 100 * EXISTENTIAL_DEPOSIT,
+					asset: Some(CommunityAsset::get()),
+				},
+			),
+		]
+	}
+
+	fn authorize_call(id: TrackId, call: &RuntimeCall) -> bool {
+		match (id, call) {
+			(0, RuntimeCall::System(frame_system::Call::remark { .. })) => true,
+			(0, RuntimeCall::System(frame_system::Call::remark_with_event { .. })) => true,
+			(1, RuntimeCall::Balances(_)) => true,
+			(2, _) => true,
+			(3, RuntimeCall::Assets(_)) => true,
+			_ => false,
+		}
+	}
+}
+
 /// Configure the pallet-voting in pallets/voting.
 impl pallet_voting::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type NativeBalance = Balances;
+	type Assets = Assets;
 	type BlockNumberToBalance = sp_runtime::traits::ConvertInto;
 	type RuntimeFreezeReason = RuntimeFreezeReason;
+	type RuntimeHoldReason = RuntimeHoldReason;
 	type MaxVotes = ConstU32<100>;
-	type ProposalDuration = ConstU32<90000>;
-	type ProposalId = u64;
+	type Tracks = VotingTracks;
+	type ProposalId = ProposalId;
+	type MaxTitleLength = ConstU32<128>;
+	// Longer descriptions are noted with the preimage pallet, which takes a deposit.
+	type MaxDescriptionLength = ConstU32<4096>;
+	type MaxProposalsEndingPerBlock = ConstU32<100>;
+	type MaxAutoClosePerBlock = ConstU32<10>;
+	type Preimages = Preimage;
+	type Scheduler = Scheduler;
+	type EnactmentOrigin = EnactmentOrigin;
+	type MaxRegistrationBatch = ConstU32<1000>;
+	// Registrar accounts managed with `set_registrar` can register voters too, so onboarding
+	// doesn't need the sudo key.
+	type RegistrarOrigin = EnsureRoot<AccountId>;
+	type RoundOrigin = EnsureRoot<AccountId>;
+	type MaxProposalsPerRound = ConstU32<16>;
+	// Slashed deposits are burned.
+	type Slash = ();
+	type CancelOrigin = EnsureRoot<AccountId>;
+	type WithdrawalPeriod = ConstU32<{ 2 * HOURS }>;
+	type IdentityProvider = IdentityJudgement<Runtime, VoterJudgement>;
+	type EnactmentPeriod = ConstU32<{ 2 * DAYS }>;
+	// Winning votes with the highest conviction stay locked for 32 days.
+	type VoteLockingPeriod = ConstU32<DAYS>;
+	type LockMode = VoteLockMode;
+	// Votes are counted whole, as before `vote_with_amount`. Raising it requires migrating the
+	// open tallies and the votes already cast.
+	type VotePrecision = ConstU32<1>;
+	type WeightInfo = pallet_voting::weights::SubstrateWeight<Runtime>;
 }
 
 pub struct AuthorityToAccount;
@@ -333,6 +525,9 @@ construct_runtime!(
 		TransactionPayment: pallet_transaction_payment,
 		Sudo: pallet_sudo,
 		Assets: pallet_assets,
+		Preimage: pallet_preimage,
+		Scheduler: pallet_scheduler,
+		Identity: pallet_identity,
 		Voting: pallet_voting,
 	}
 );
@@ -360,6 +555,23 @@ pub type UncheckedExtrinsic =
 	generic::UncheckedExtrinsic<Address, RuntimeCall, Signature, SignedExtra>;
 /// The payload being signed in transactions.
 pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
+/// Migrations to apply on runtime upgrade.
+pub type Migrations = (
+	pallet_voting::migrations::v1::MigrateToV1<Runtime>,
+	pallet_voting::migrations::v2::MigrateToV2<Runtime>,
+	pallet_voting::migrations::v3::MigrateToV3<Runtime>,
+	pallet_voting::migrations::v4::MigrateToV4<Runtime>,
+	pallet_voting::migrations::v5::MigrateToV5<Runtime>,
+	pallet_voting::migrations::v6::MigrateToV6<Runtime>,
+	pallet_voting::migrations::v7::MigrateToV7<Runtime>,
+	pallet_voting::migrations::v8::MigrateToV8<Runtime>,
+	pallet_voting::migrations::v9::MigrateToV9<Runtime>,
+	pallet_voting::migrations::v10::MigrateToV10<Runtime>,
+	pallet_voting::migrations::v11::MigrateToV11<Runtime>,
+	pallet_voting::migrations::v12::MigrateToV12<Runtime>,
+	pallet_voting::migrations::v13::MigrateToV13<Runtime>,
+);
+
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -367,6 +579,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -381,6 +594,9 @@ mod benches {
 		[pallet_balances, Balances]
 		[pallet_timestamp, Timestamp]
 		[pallet_sudo, Sudo]
+		[pallet_preimage, Preimage]
+		[pallet_identity, Identity]
+		[pallet_scheduler, Scheduler]
 		[pallet_voting, Voting]
 	);
 }
@@ -553,6 +769,43 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_voting_runtime_api::VotingApi<Block, AccountId, Balance, BlockNumber, Hash, ProposalId>
+		for Runtime
+	{
+		fn proposal(
+			proposal_id: ProposalId,
+		) -> Option<pallet_voting_runtime_api::ProposalInfo<Hash, Balance, BlockNumber>> {
+			Voting::proposal_info(proposal_id)
+		}
+		fn tally(proposal_id: ProposalId) -> Option<pallet_voting_runtime_api::Tally<Balance>> {
+			Voting::tally(proposal_id)
+		}
+		fn active_proposals() -> Vec<ProposalId> {
+			Voting::active_proposals()
+		}
+		fn voter_history(
+			who: AccountId,
+		) -> Vec<pallet_voting_runtime_api::VoteInfo<ProposalId, Balance, BlockNumber>> {
+			Voting::voter_history(&who)
+		}
+		fn is_registered(who: AccountId) -> bool {
+			Voting::is_registered(&who)
+		}
+		fn vote_cost(votes: Balance) -> Option<Balance> {
+			Voting::vote_cost(votes)
+		}
+		fn vote_shortfall(
+			who: AccountId,
+			proposal_id: ProposalId,
+			votes: Balance,
+		) -> Option<Balance> {
+			Voting::vote_shortfall(&who, proposal_id, votes)
+		}
+		fn frozen_balance(who: AccountId) -> Balance {
+			Voting::frozen_balance(&who)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (